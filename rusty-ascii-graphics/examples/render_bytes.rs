@@ -0,0 +1,80 @@
+//! Compares bytes written by full redraws and by diff rendering
+//! for a scene where a few things move each frame.
+//!
+//! Run with `cargo run --release --example render_bytes`
+use std::time::Instant;
+
+use crossterm::style::{Color, Colors};
+use rusty_ascii_graphics::{RectangleShape, RenderBuffer, RenderChar};
+
+const WIDTH: usize = 120;
+const HEIGHT: usize = 40;
+const FRAMES: usize = 300;
+
+fn fg(color: Color) -> Colors {
+    Colors {
+        foreground: Some(color),
+        background: None,
+    }
+}
+
+// Border, static food and a snake crawling around
+fn draw_frame(buffer: &mut RenderBuffer, frame: usize) {
+    buffer.clear(RenderChar::empty());
+    let border = RenderChar::new('#', fg(Color::Blue));
+    buffer.draw(&RectangleShape::new(
+        0,
+        0,
+        WIDTH as isize,
+        HEIGHT as isize,
+        border,
+        false,
+    ));
+    for i in 0..20 {
+        let (x, y) = ((i * 37) % (WIDTH - 2) + 1, (i * 11) % (HEIGHT - 2) + 1);
+        buffer.put(x as isize, y as isize, RenderChar::new('*', fg(Color::Red)));
+    }
+    for part in 0..15 {
+        let pos = (frame + part) % ((WIDTH - 2) * (HEIGHT - 2));
+        let (x, y) = (pos % (WIDTH - 2) + 1, pos / (WIDTH - 2) + 1);
+        let c = if part == 14 { '@' } else { 'o' };
+        buffer.put(x as isize, y as isize, RenderChar::new(c, fg(Color::Green)));
+    }
+    let text = format!("Frame {:>5}", frame);
+    for (i, c) in text.chars().enumerate() {
+        buffer.put(2 + i as isize, 0, RenderChar::new(c, fg(Color::Grey)));
+    }
+}
+
+fn measure(diff: bool) -> (usize, u128) {
+    let mut buffer = RenderBuffer::new(WIDTH, HEIGHT);
+    let mut out: Vec<u8> = Vec::new();
+    let start = Instant::now();
+    for frame in 0..FRAMES {
+        draw_frame(&mut buffer, frame);
+        if !diff {
+            buffer.invalidate();
+        }
+        buffer.render_to(&mut out);
+    }
+    (out.len(), start.elapsed().as_micros())
+}
+
+fn main() {
+    let (full, full_time) = measure(false);
+    let (diff, diff_time) = measure(true);
+    println!("{} frames of {}x{}", FRAMES, WIDTH, HEIGHT);
+    println!(
+        "full redraw: {:>9} bytes, {:>7} bytes/frame, {:>6} us",
+        full,
+        full / FRAMES,
+        full_time
+    );
+    println!(
+        "diff:        {:>9} bytes, {:>7} bytes/frame, {:>6} us",
+        diff,
+        diff / FRAMES,
+        diff_time
+    );
+    println!("reduction:   {:.1}x", full as f64 / diff as f64);
+}
@@ -0,0 +1,354 @@
+//! Bundled bitmap font. Printable ascii comes from the public domain
+//! font8x8 set, with every row doubled to get terminal-like cells.
+//! Box drawing, block elements and braille are built from their shape
+
+/// Pixel size of one cell
+pub const CELL_WIDTH: usize = 8;
+pub const CELL_HEIGHT: usize = 16;
+
+/// Rows of cell from top, lowest bit is leftmost pixel
+pub type Glyph = [u8; CELL_HEIGHT];
+
+/// Glyphs of ' ' to '~', 8x8 pixels
+const ASCII: [[u8; 8]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+    [0x18, 0x3C, 0x3C, 0x18, 0x18, 0x00, 0x18, 0x00],
+    [0x36, 0x36, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+    [0x36, 0x36, 0x7F, 0x36, 0x7F, 0x36, 0x36, 0x00],
+    [0x0C, 0x3E, 0x03, 0x1E, 0x30, 0x1F, 0x0C, 0x00],
+    [0x00, 0x63, 0x33, 0x18, 0x0C, 0x66, 0x63, 0x00],
+    [0x1C, 0x36, 0x1C, 0x6E, 0x3B, 0x33, 0x6E, 0x00],
+    [0x06, 0x06, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00],
+    [0x18, 0x0C, 0x06, 0x06, 0x06, 0x0C, 0x18, 0x00],
+    [0x06, 0x0C, 0x18, 0x18, 0x18, 0x0C, 0x06, 0x00],
+    [0x00, 0x66, 0x3C, 0xFF, 0x3C, 0x66, 0x00, 0x00],
+    [0x00, 0x0C, 0x0C, 0x3F, 0x0C, 0x0C, 0x00, 0x00],
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C, 0x06],
+    [0x00, 0x00, 0x00, 0x3F, 0x00, 0x00, 0x00, 0x00],
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C, 0x00],
+    [0x60, 0x30, 0x18, 0x0C, 0x06, 0x03, 0x01, 0x00],
+    [0x3E, 0x63, 0x73, 0x7B, 0x6F, 0x67, 0x3E, 0x00],
+    [0x0C, 0x0E, 0x0C, 0x0C, 0x0C, 0x0C, 0x3F, 0x00],
+    [0x1E, 0x33, 0x30, 0x1C, 0x06, 0x33, 0x3F, 0x00],
+    [0x1E, 0x33, 0x30, 0x1C, 0x30, 0x33, 0x1E, 0x00],
+    [0x38, 0x3C, 0x36, 0x33, 0x7F, 0x30, 0x78, 0x00],
+    [0x3F, 0x03, 0x1F, 0x30, 0x30, 0x33, 0x1E, 0x00],
+    [0x1C, 0x06, 0x03, 0x1F, 0x33, 0x33, 0x1E, 0x00],
+    [0x3F, 0x33, 0x30, 0x18, 0x0C, 0x0C, 0x0C, 0x00],
+    [0x1E, 0x33, 0x33, 0x1E, 0x33, 0x33, 0x1E, 0x00],
+    [0x1E, 0x33, 0x33, 0x3E, 0x30, 0x18, 0x0E, 0x00],
+    [0x00, 0x0C, 0x0C, 0x00, 0x00, 0x0C, 0x0C, 0x00],
+    [0x00, 0x0C, 0x0C, 0x00, 0x00, 0x0C, 0x0C, 0x06],
+    [0x18, 0x0C, 0x06, 0x03, 0x06, 0x0C, 0x18, 0x00],
+    [0x00, 0x00, 0x3F, 0x00, 0x00, 0x3F, 0x00, 0x00],
+    [0x06, 0x0C, 0x18, 0x30, 0x18, 0x0C, 0x06, 0x00],
+    [0x1E, 0x33, 0x30, 0x18, 0x0C, 0x00, 0x0C, 0x00],
+    [0x3E, 0x63, 0x7B, 0x7B, 0x7B, 0x03, 0x1E, 0x00],
+    [0x0C, 0x1E, 0x33, 0x33, 0x3F, 0x33, 0x33, 0x00],
+    [0x3F, 0x66, 0x66, 0x3E, 0x66, 0x66, 0x3F, 0x00],
+    [0x3C, 0x66, 0x03, 0x03, 0x03, 0x66, 0x3C, 0x00],
+    [0x1F, 0x36, 0x66, 0x66, 0x66, 0x36, 0x1F, 0x00],
+    [0x7F, 0x46, 0x16, 0x1E, 0x16, 0x46, 0x7F, 0x00],
+    [0x7F, 0x46, 0x16, 0x1E, 0x16, 0x06, 0x0F, 0x00],
+    [0x3C, 0x66, 0x03, 0x03, 0x73, 0x66, 0x7C, 0x00],
+    [0x33, 0x33, 0x33, 0x3F, 0x33, 0x33, 0x33, 0x00],
+    [0x1E, 0x0C, 0x0C, 0x0C, 0x0C, 0x0C, 0x1E, 0x00],
+    [0x78, 0x30, 0x30, 0x30, 0x33, 0x33, 0x1E, 0x00],
+    [0x67, 0x66, 0x36, 0x1E, 0x36, 0x66, 0x67, 0x00],
+    [0x0F, 0x06, 0x06, 0x06, 0x46, 0x66, 0x7F, 0x00],
+    [0x63, 0x77, 0x7F, 0x7F, 0x6B, 0x63, 0x63, 0x00],
+    [0x63, 0x67, 0x6F, 0x7B, 0x73, 0x63, 0x63, 0x00],
+    [0x1C, 0x36, 0x63, 0x63, 0x63, 0x36, 0x1C, 0x00],
+    [0x3F, 0x66, 0x66, 0x3E, 0x06, 0x06, 0x0F, 0x00],
+    [0x1E, 0x33, 0x33, 0x33, 0x3B, 0x1E, 0x38, 0x00],
+    [0x3F, 0x66, 0x66, 0x3E, 0x36, 0x66, 0x67, 0x00],
+    [0x1E, 0x33, 0x07, 0x0E, 0x38, 0x33, 0x1E, 0x00],
+    [0x3F, 0x2D, 0x0C, 0x0C, 0x0C, 0x0C, 0x1E, 0x00],
+    [0x33, 0x33, 0x33, 0x33, 0x33, 0x33, 0x3F, 0x00],
+    [0x33, 0x33, 0x33, 0x33, 0x33, 0x1E, 0x0C, 0x00],
+    [0x63, 0x63, 0x63, 0x6B, 0x7F, 0x77, 0x63, 0x00],
+    [0x63, 0x63, 0x36, 0x1C, 0x1C, 0x36, 0x63, 0x00],
+    [0x33, 0x33, 0x33, 0x1E, 0x0C, 0x0C, 0x1E, 0x00],
+    [0x7F, 0x63, 0x31, 0x18, 0x4C, 0x66, 0x7F, 0x00],
+    [0x1E, 0x06, 0x06, 0x06, 0x06, 0x06, 0x1E, 0x00],
+    [0x03, 0x06, 0x0C, 0x18, 0x30, 0x60, 0x40, 0x00],
+    [0x1E, 0x18, 0x18, 0x18, 0x18, 0x18, 0x1E, 0x00],
+    [0x08, 0x1C, 0x36, 0x63, 0x00, 0x00, 0x00, 0x00],
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFF],
+    [0x0C, 0x0C, 0x18, 0x00, 0x00, 0x00, 0x00, 0x00],
+    [0x00, 0x00, 0x1E, 0x30, 0x3E, 0x33, 0x6E, 0x00],
+    [0x07, 0x06, 0x06, 0x3E, 0x66, 0x66, 0x3B, 0x00],
+    [0x00, 0x00, 0x1E, 0x33, 0x03, 0x33, 0x1E, 0x00],
+    [0x38, 0x30, 0x30, 0x3E, 0x33, 0x33, 0x6E, 0x00],
+    [0x00, 0x00, 0x1E, 0x33, 0x3F, 0x03, 0x1E, 0x00],
+    [0x1C, 0x36, 0x06, 0x0F, 0x06, 0x06, 0x0F, 0x00],
+    [0x00, 0x00, 0x6E, 0x33, 0x33, 0x3E, 0x30, 0x1F],
+    [0x07, 0x06, 0x36, 0x6E, 0x66, 0x66, 0x67, 0x00],
+    [0x0C, 0x00, 0x0E, 0x0C, 0x0C, 0x0C, 0x1E, 0x00],
+    [0x30, 0x00, 0x30, 0x30, 0x30, 0x33, 0x33, 0x1E],
+    [0x07, 0x06, 0x66, 0x36, 0x1E, 0x36, 0x67, 0x00],
+    [0x0E, 0x0C, 0x0C, 0x0C, 0x0C, 0x0C, 0x1E, 0x00],
+    [0x00, 0x00, 0x33, 0x7F, 0x7F, 0x6B, 0x63, 0x00],
+    [0x00, 0x00, 0x1F, 0x33, 0x33, 0x33, 0x33, 0x00],
+    [0x00, 0x00, 0x1E, 0x33, 0x33, 0x33, 0x1E, 0x00],
+    [0x00, 0x00, 0x3B, 0x66, 0x66, 0x3E, 0x06, 0x0F],
+    [0x00, 0x00, 0x6E, 0x33, 0x33, 0x3E, 0x30, 0x78],
+    [0x00, 0x00, 0x3B, 0x6E, 0x66, 0x06, 0x0F, 0x00],
+    [0x00, 0x00, 0x3E, 0x03, 0x1E, 0x30, 0x1F, 0x00],
+    [0x08, 0x0C, 0x3E, 0x0C, 0x0C, 0x2C, 0x18, 0x00],
+    [0x00, 0x00, 0x33, 0x33, 0x33, 0x33, 0x6E, 0x00],
+    [0x00, 0x00, 0x33, 0x33, 0x33, 0x1E, 0x0C, 0x00],
+    [0x00, 0x00, 0x63, 0x6B, 0x7F, 0x7F, 0x36, 0x00],
+    [0x00, 0x00, 0x63, 0x36, 0x1C, 0x36, 0x63, 0x00],
+    [0x00, 0x00, 0x33, 0x33, 0x33, 0x3E, 0x30, 0x1F],
+    [0x00, 0x00, 0x3F, 0x19, 0x0C, 0x26, 0x3F, 0x00],
+    [0x38, 0x0C, 0x0C, 0x07, 0x0C, 0x0C, 0x38, 0x00],
+    [0x18, 0x18, 0x18, 0x00, 0x18, 0x18, 0x18, 0x00],
+    [0x07, 0x0C, 0x0C, 0x38, 0x0C, 0x0C, 0x07, 0x00],
+    [0x6E, 0x3B, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+];
+
+/// Few symbols outside ascii used by game, 8x8 pixels
+const SYMBOLS: [(char, [u8; 8]); 4] = [
+    ('·', [0x00, 0x00, 0x00, 0x18, 0x18, 0x00, 0x00, 0x00]),
+    ('•', [0x00, 0x00, 0x18, 0x3C, 0x3C, 0x18, 0x00, 0x00]),
+    ('…', [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x92, 0x00]),
+    ('※', [0x08, 0x22, 0x14, 0x49, 0x14, 0x22, 0x08, 0x00]),
+];
+
+/// Glyph drawn for char, `None` when font doesn't have it
+pub fn glyph(c: char) -> Option<Glyph> {
+    match c {
+        ' '..='~' => Some(doubled(&ASCII[c as usize - ' ' as usize])),
+        '\u{2500}'..='\u{257F}' => box_drawing(c),
+        '\u{2580}'..='\u{259F}' => block(c),
+        '\u{2800}'..='\u{28FF}' => Some(braille(c as u32 - 0x2800)),
+        _ => SYMBOLS
+            .iter()
+            .find(|(symbol, _)| *symbol == c)
+            .map(|(_, rows)| doubled(rows)),
+    }
+}
+
+/// Outline over `width` cells, drawn for chars font doesn't have
+pub fn missing(width: usize) -> Vec<Glyph> {
+    let right = width * CELL_WIDTH - 2;
+    (0..width)
+        .map(|i| {
+            let mut glyph = [0; CELL_HEIGHT];
+            for (y, row) in glyph.iter_mut().enumerate().take(CELL_HEIGHT - 1).skip(1) {
+                for x in 0..CELL_WIDTH {
+                    let px = i * CELL_WIDTH + x;
+                    let edge = y == 1 || y == CELL_HEIGHT - 2;
+                    if (1..=right).contains(&px) && (edge || px == 1 || px == right) {
+                        *row |= 1 << x;
+                    }
+                }
+            }
+            glyph
+        })
+        .collect()
+}
+
+fn doubled(rows: &[u8; 8]) -> Glyph {
+    let mut glyph = [0; CELL_HEIGHT];
+    for (i, row) in rows.iter().enumerate() {
+        glyph[i * 2] = *row;
+        glyph[i * 2 + 1] = *row;
+    }
+    glyph
+}
+
+/// Pixels from `from` to `to` set, excluding `to`
+fn span(from: usize, to: usize) -> u8 {
+    (from..to).fold(0, |bits, x| bits | 1 << x)
+}
+
+// Kind of line leaving cell through each side: up, right, down, left.
+// 1 is light, 2 heavy and 3 double
+fn arms(c: char) -> Option<[u8; 4]> {
+    Some(match c {
+        '─' => [0, 1, 0, 1],
+        '│' => [1, 0, 1, 0],
+        '┌' | '╭' => [0, 1, 1, 0],
+        '┐' | '╮' => [0, 0, 1, 1],
+        '└' | '╰' => [1, 1, 0, 0],
+        '┘' | '╯' => [1, 0, 0, 1],
+        '├' => [1, 1, 1, 0],
+        '┤' => [1, 0, 1, 1],
+        '┬' => [0, 1, 1, 1],
+        '┴' => [1, 1, 0, 1],
+        '┼' => [1, 1, 1, 1],
+        '━' => [0, 2, 0, 2],
+        '┃' => [2, 0, 2, 0],
+        '┏' => [0, 2, 2, 0],
+        '┓' => [0, 0, 2, 2],
+        '┗' => [2, 2, 0, 0],
+        '┛' => [2, 0, 0, 2],
+        '┣' => [2, 2, 2, 0],
+        '┫' => [2, 0, 2, 2],
+        '┳' => [0, 2, 2, 2],
+        '┻' => [2, 2, 0, 2],
+        '╋' => [2, 2, 2, 2],
+        '═' => [0, 3, 0, 3],
+        '║' => [3, 0, 3, 0],
+        '╔' => [0, 3, 3, 0],
+        '╗' => [0, 0, 3, 3],
+        '╚' => [3, 3, 0, 0],
+        '╝' => [3, 0, 0, 3],
+        '╠' => [3, 3, 3, 0],
+        '╣' => [3, 0, 3, 3],
+        '╦' => [0, 3, 3, 3],
+        '╩' => [3, 3, 0, 3],
+        '╬' => [3, 3, 3, 3],
+        '╴' => [0, 0, 0, 1],
+        '╵' => [1, 0, 0, 0],
+        '╶' => [0, 1, 0, 0],
+        '╷' => [0, 0, 1, 0],
+        _ => return None,
+    })
+}
+
+// Pixels lines of each kind take across the cell, start and end of
+// columns of vertical lines and rows of horizontal ones
+fn lanes(kind: u8, middle: usize) -> Vec<(usize, usize)> {
+    match kind {
+        0 | 1 => vec![(middle, middle + 1)],
+        2 => vec![(middle, middle + 2)],
+        _ => vec![(middle - 1, middle), (middle + 2, middle + 3)],
+    }
+}
+
+// Arms reach over lines crossing them, so they always join. Lanes of
+// double lines stop at the near crossing line on sides with an arm
+fn box_drawing(c: char) -> Option<Glyph> {
+    let [up, right, down, left] = arms(c)?;
+    let mut glyph = [0; CELL_HEIGHT];
+    let rows = lanes(left.max(right), 7);
+    let columns = lanes(up.max(down), 3);
+    // Near and far edge of crossing lanes, from the start or end side
+    let reach = |lanes: &[(usize, usize)], from_start: bool, near: bool| {
+        let (first, last) = (lanes[0], lanes[lanes.len() - 1]);
+        match (from_start, near) {
+            (true, true) => first.1,
+            (true, false) => last.1,
+            (false, true) => last.0,
+            (false, false) => first.0,
+        }
+    };
+    // Whether lane at index has an arm on its side
+    let sided = |kind: u8, i: usize, sides: [u8; 2]| kind == 3 && sides[i] != 0;
+    for (kind, from_start) in [(up, true), (down, false)] {
+        if kind == 0 {
+            continue;
+        }
+        for (i, (start, end)) in lanes(kind, 3).into_iter().enumerate() {
+            let stop = reach(&rows, from_start, sided(kind, i, [left, right]));
+            let range = if from_start {
+                0..stop
+            } else {
+                stop..CELL_HEIGHT
+            };
+            for row in &mut glyph[range] {
+                *row |= span(start, end);
+            }
+        }
+    }
+    for (kind, from_start) in [(left, true), (right, false)] {
+        if kind == 0 {
+            continue;
+        }
+        for (i, (start, end)) in lanes(kind, 7).into_iter().enumerate() {
+            let stop = reach(&columns, from_start, sided(kind, i, [up, down]));
+            let (from, to) = if from_start {
+                (0, stop)
+            } else {
+                (stop, CELL_WIDTH)
+            };
+            for row in &mut glyph[start..end] {
+                *row |= span(from, to);
+            }
+        }
+    }
+    Some(glyph)
+}
+
+// Shaded blocks are dithered, the rest are filled parts of the cell
+fn block(c: char) -> Option<Glyph> {
+    let code = c as u32;
+    let mut glyph = [0; CELL_HEIGHT];
+    match c {
+        '▀' => glyph[..8].fill(0xFF),
+        // Lower eighths, up to full block
+        '\u{2581}'..='\u{2588}' => {
+            let rows = (code - 0x2580) as usize * 2;
+            glyph[CELL_HEIGHT - rows..].fill(0xFF);
+        }
+        // Left eighths, from seven down to one
+        '\u{2589}'..='\u{258F}' => glyph.fill(span(0, (0x2590 - code) as usize)),
+        '▐' => glyph.fill(0xF0),
+        '░' | '▒' | '▓' => {
+            for (y, row) in glyph.iter_mut().enumerate() {
+                for x in 0..CELL_WIDTH {
+                    let set = match c {
+                        '░' => (x + 2 * y) % 4 == 0,
+                        '▒' => (x + y) % 2 == 0,
+                        _ => (x + 2 * y) % 4 != 0,
+                    };
+                    if set {
+                        *row |= 1 << x;
+                    }
+                }
+            }
+        }
+        '▔' => glyph[..2].fill(0xFF),
+        '▕' => glyph.fill(0x80),
+        _ => {
+            // Quadrants as top left, top right, bottom left, bottom right
+            let quadrants: u8 = match c {
+                '▖' => 0b0100,
+                '▗' => 0b1000,
+                '▘' => 0b0001,
+                '▙' => 0b1101,
+                '▚' => 0b1001,
+                '▛' => 0b0111,
+                '▜' => 0b1011,
+                '▝' => 0b0010,
+                '▞' => 0b0110,
+                '▟' => 0b1110,
+                _ => return None,
+            };
+            for (y, row) in glyph.iter_mut().enumerate() {
+                let shift = if y < CELL_HEIGHT / 2 { 0 } else { 2 };
+                let bits = quadrants >> shift;
+                *row = if bits & 1 != 0 { 0x0F } else { 0 } | if bits & 2 != 0 { 0xF0 } else { 0 };
+            }
+        }
+    }
+    Some(glyph)
+}
+
+// Dots numbered down the left column then the right one,
+// with the bottom row added last
+fn braille(dots: u32) -> Glyph {
+    const POSITIONS: [(usize, usize); 8] = [
+        (0, 0),
+        (0, 1),
+        (0, 2),
+        (1, 0),
+        (1, 1),
+        (1, 2),
+        (0, 3),
+        (1, 3),
+    ];
+    let mut glyph = [0; CELL_HEIGHT];
+    for (bit, (x, y)) in POSITIONS.iter().enumerate() {
+        if dots & 1 << bit != 0 {
+            let top = 1 + y * 4;
+            for row in &mut glyph[top..top + 2] {
+                *row |= span(1 + x * 4, 3 + x * 4);
+            }
+        }
+    }
+    glyph
+}
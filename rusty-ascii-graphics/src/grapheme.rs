@@ -0,0 +1,110 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use unicode_width::UnicodeWidthChar;
+
+static WIDE_AMBIGUOUS: AtomicBool = AtomicBool::new(false);
+
+/// Treat chars of ambiguous width, like `※`, as double width.
+/// Depends on terminal font, most western terminals draw them narrow
+pub fn set_wide_ambiguous(wide: bool) {
+    WIDE_AMBIGUOUS.store(wide, Ordering::Relaxed);
+}
+
+/// Columns taken by char on terminal. Control and combining chars
+/// count as one, since they are drawn as one cell
+pub fn char_width(c: char) -> usize {
+    let width = if WIDE_AMBIGUOUS.load(Ordering::Relaxed) {
+        c.width_cjk()
+    } else {
+        c.width()
+    };
+    width.unwrap_or(1).max(1)
+}
+
+const ZWJ: char = '\u{200D}';
+/// Variation selector asking for emoji presentation of previous char
+const EMOJI_PRESENTATION: char = '\u{FE0F}';
+
+fn is_regional_indicator(c: char) -> bool {
+    ('\u{1F1E6}'..='\u{1F1FF}').contains(&c)
+}
+
+fn is_skin_tone(c: char) -> bool {
+    ('\u{1F3FB}'..='\u{1F3FF}').contains(&c)
+}
+
+// Whether char continues cluster that ends with `prev`
+fn extends(prev: char, c: char, indicators: usize) -> bool {
+    if prev.is_control() || c.is_control() {
+        return false;
+    }
+    c.width() == Some(0)
+        || is_skin_tone(c)
+        || prev == ZWJ
+        || (is_regional_indicator(c) && indicators % 2 == 1)
+}
+
+/// Split text into grapheme clusters: chars with combining marks, emoji
+/// with modifiers, joined emoji sequences and flags. Simplified version
+/// of Unicode rules, enough for text drawn on terminal
+pub fn graphemes(s: &str) -> impl Iterator<Item = &str> {
+    let mut rest = s;
+    std::iter::from_fn(move || {
+        let mut chars = rest.char_indices();
+        let (_, mut prev) = chars.next()?;
+        let mut indicators = is_regional_indicator(prev) as usize;
+        let mut end = rest.len();
+        for (i, c) in chars {
+            if !extends(prev, c, indicators) {
+                end = i;
+                break;
+            }
+            indicators += is_regional_indicator(c) as usize;
+            prev = c;
+        }
+        let (cluster, tail) = rest.split_at(end);
+        rest = tail;
+        Some(cluster)
+    })
+}
+
+/// Columns taken by grapheme cluster on terminal, one or two
+pub fn grapheme_width(grapheme: &str) -> usize {
+    let mut chars = grapheme.chars();
+    let Some(first) = chars.next() else {
+        return 0;
+    };
+    let emoji = chars.any(|c| c == EMOJI_PRESENTATION)
+        || (is_regional_indicator(first) && grapheme.chars().count() > 1);
+    if emoji {
+        2
+    } else {
+        char_width(first).min(2)
+    }
+}
+
+/// Columns taken by string on terminal
+pub fn str_width(s: &str) -> usize {
+    graphemes(s).map(grapheme_width).sum()
+}
+
+/// Cut text into pieces at most `width` columns wide, never splitting
+/// a grapheme cluster. Clusters wider than `width` get a piece of their own
+pub fn split_width(s: &str, width: usize) -> Vec<&str> {
+    let mut pieces = Vec::new();
+    let (mut start, mut end, mut columns) = (0, 0, 0);
+    for g in graphemes(s) {
+        let w = grapheme_width(g);
+        if columns + w > width && end > start {
+            pieces.push(&s[start..end]);
+            start = end;
+            columns = 0;
+        }
+        end += g.len();
+        columns += w;
+    }
+    if end > start {
+        pieces.push(&s[start..end]);
+    }
+    pieces
+}
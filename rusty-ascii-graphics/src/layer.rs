@@ -0,0 +1,157 @@
+use crate::{Drawable, RenderBuffer, RenderChar};
+
+/// Area of buffer cells
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Rect {
+    pub x: isize,
+    pub y: isize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Rect {
+    pub fn new(x: isize, y: isize, width: usize, height: usize) -> Rect {
+        Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    pub fn contains(&self, x: isize, y: isize) -> bool {
+        x >= self.x
+            && y >= self.y
+            && x < self.x + self.width as isize
+            && y < self.y + self.height as isize
+    }
+}
+
+/// Buffer drawn on its own and composited into another one at its position.
+/// Starts fully transparent, so only what was drawn on it shows
+pub struct Layer {
+    pub name: &'static str,
+    pub rect: Rect,
+    /// Layers with higher z are composited on top
+    pub z: i32,
+    /// Part of target layer may change, in target coordinates
+    pub clip: Option<Rect>,
+    /// Takes pointer in its whole rect, even where nothing is drawn
+    pub solid: bool,
+    pub buffer: RenderBuffer,
+}
+
+impl Layer {
+    pub fn new(name: &'static str, rect: Rect, z: i32) -> Layer {
+        let mut buffer = RenderBuffer::new(rect.width, rect.height);
+        buffer.clear(RenderChar::transparent());
+        Layer {
+            name,
+            rect,
+            z,
+            clip: None,
+            solid: false,
+            buffer,
+        }
+    }
+
+    pub fn clip(mut self, clip: Rect) -> Layer {
+        self.clip = Some(clip);
+        self
+    }
+
+    pub fn solid(mut self) -> Layer {
+        self.solid = true;
+        self
+    }
+
+    /// Draw Drawable object on this layer, in its own coordinates
+    pub fn draw<T: Drawable>(&mut self, object: &T) {
+        self.buffer.draw(object);
+    }
+
+    /// Position inside layer of target position, when layer covers it
+    /// with something other than fully transparent cell, or is solid
+    pub fn hit(&self, x: isize, y: isize) -> Option<(isize, isize)> {
+        if !self.rect.contains(x, y) || self.clip.is_some_and(|c| !c.contains(x, y)) {
+            return None;
+        }
+        let (lx, ly) = (x - self.rect.x, y - self.rect.y);
+        let c = self.buffer.get(lx, ly);
+        if !self.solid && c.is_transparent() && c.style.bg.is_none() {
+            return None;
+        }
+        Some((lx, ly))
+    }
+}
+
+impl Drawable for Layer {
+    fn draw(&self, render: &mut RenderBuffer) {
+        for ly in 0..self.rect.height {
+            for lx in 0..self.rect.width {
+                let (x, y) = (self.rect.x + lx as isize, self.rect.y + ly as isize);
+                if self.clip.is_some_and(|c| !c.contains(x, y)) {
+                    continue;
+                }
+                let mut c = self.buffer.get(lx as isize, ly as isize);
+                // Wide glyph cut by clip would cover cell outside of it
+                if c.width() == 2 && self.clip.is_some_and(|c| !c.contains(x + 1, y)) {
+                    c = c.with_char(' ');
+                }
+                render.put(x, y, c);
+            }
+        }
+    }
+}
+
+/// Layers composited in z order, equal z in order they were added
+#[derive(Default)]
+pub struct Compositor {
+    layers: Vec<Layer>,
+}
+
+impl Compositor {
+    pub fn new() -> Compositor {
+        Compositor { layers: Vec::new() }
+    }
+
+    /// Add layer and get it back to draw on
+    pub fn add(&mut self, layer: Layer) -> &mut Layer {
+        self.layers.push(layer);
+        self.layers.last_mut().unwrap()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Layer> {
+        self.layers.iter().find(|l| l.name == name)
+    }
+
+    /// Remove all layers
+    pub fn clear(&mut self) {
+        self.layers.clear();
+    }
+
+    // Layer indices from bottom to top
+    fn order(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.layers.len()).collect();
+        order.sort_by_key(|&i| self.layers[i].z);
+        order
+    }
+
+    /// Topmost layer showing something at target position,
+    /// with position inside that layer
+    pub fn hit(&self, x: isize, y: isize) -> Option<(&Layer, (isize, isize))> {
+        self.order()
+            .into_iter()
+            .rev()
+            .map(|i| &self.layers[i])
+            .find_map(|l| Some((l, l.hit(x, y)?)))
+    }
+}
+
+impl Drawable for Compositor {
+    fn draw(&self, render: &mut RenderBuffer) {
+        for i in self.order() {
+            render.draw(&self.layers[i]);
+        }
+    }
+}
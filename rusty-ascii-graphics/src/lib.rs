@@ -1,44 +1,145 @@
 // TODO: Fix windows compatibility
 // Issues: Everything seems broken
+use std::fmt;
+
 use crossterm::{
-    style::{Colors, ResetColor, SetColors, Print},
+    cursor::MoveTo,
+    style::{Colors, Print, ResetColor},
     QueueableCommand,
 };
 
+#[cfg(feature = "export")]
+mod font;
+mod grapheme;
+mod layer;
+#[cfg(feature = "export")]
+mod raster;
+mod scale;
+mod shapes;
+mod style;
+mod text;
+mod widget;
+#[cfg(feature = "export")]
+pub use font::{CELL_HEIGHT, CELL_WIDTH};
+pub use grapheme::{
+    char_width, grapheme_width, graphemes, set_wide_ambiguous, split_width, str_width,
+};
+pub use layer::{Compositor, Layer, Rect};
+#[cfg(feature = "export")]
+pub use raster::{Image, Rasterizer, Recording};
+pub use scale::{PixelGrid, Sample, ScaleMode, Scaled};
+pub use shapes::{path_joins, BorderStyle, BoxShape, Ellipse, Line, Polyline};
+pub use style::{ColorDepth, Style};
+pub use text::{Align, Span, StyledText};
+pub use widget::{Button, Checkbox, Focus, ListView, Response, TextInput, Widget};
+
+use style::set_style;
+
 /// Traits that require object to be rendered on given buffer
 pub trait Drawable {
     fn draw(&self, render: &mut RenderBuffer);
 }
 
-#[derive(Clone)]
+/// Cell of buffer, holding one grapheme cluster. Wide glyphs take two
+/// cells, second one holding `CONTINUATION`
+#[derive(Clone, PartialEq)]
 pub struct RenderChar {
     pub char: char,
-    pub colors: Colors,
+    /// Zero width chars drawn with `char`, like combining accents,
+    /// emoji modifiers or rest of joined emoji sequence
+    pub marks: String,
+    pub style: Style,
 }
 
 impl RenderChar {
     pub fn new(char: char, colors: Colors) -> RenderChar {
+        RenderChar::styled(char, colors.into())
+    }
+    pub fn empty() -> RenderChar {
+        RenderChar::styled(' ', Style::default())
+    }
+
+    pub fn styled(char: char, style: Style) -> RenderChar {
         RenderChar {
-            char: char,
-            colors: colors,
+            char,
+            marks: String::new(),
+            style,
         }
     }
-    pub fn empty() -> RenderChar {
+
+    /// Cell holding grapheme cluster, ` ` when it's empty
+    pub fn grapheme(grapheme: &str, style: Style) -> RenderChar {
+        let mut chars = grapheme.chars();
         RenderChar {
-            char: ' ',
-            colors: Colors {
-                foreground: None,
-                background: None,
-            },
+            char: chars.next().unwrap_or(' '),
+            marks: chars.collect(),
+            style,
         }
     }
+
+    /// Text of the cell
+    pub fn text(&self) -> String {
+        let mut text = String::new();
+        self.push_to(&mut text);
+        text
+    }
+
+    fn push_to(&self, out: &mut String) {
+        out.push(self.char);
+        out.push_str(&self.marks);
+    }
+
+    /// Columns taken on terminal, one or two
+    pub fn width(&self) -> usize {
+        if self.marks.is_empty() {
+            char_width(self.char).min(2)
+        } else {
+            grapheme_width(&self.text())
+        }
+    }
+
+    pub fn is_continuation(&self) -> bool {
+        self.char == CONTINUATION
+    }
+
+    /// Cell that leaves what is below it unchanged. With background color
+    /// set only background below is changed
+    pub fn transparent() -> RenderChar {
+        RenderChar::empty().with_char(TRANSPARENT)
+    }
+
+    pub fn is_transparent(&self) -> bool {
+        self.char == TRANSPARENT
+    }
+
+    /// Same style with another char
+    pub fn with_char(mut self, char: char) -> RenderChar {
+        self.char = char;
+        self.marks.clear();
+        self
+    }
 }
 
+/// Char of transparent cells
+pub const TRANSPARENT: char = '\0';
+
+/// Char of cells covered by wide glyph in cell before them.
+/// They are never drawn, and putting them does nothing
+pub const CONTINUATION: char = '\u{1}';
+
+/// Unchanged cells shorter than this are reprinted instead of moving cursor
+/// over them, since cursor move sequence takes about as many bytes
+const MAX_REPRINT_GAP: usize = 4;
+
 /// Main RenderBuffer struct
 pub struct RenderBuffer {
     width: usize,
     height: usize,
     buffer: Vec<RenderChar>,
+    /// Frame that is on screen, empty when it's unknown
+    rendered: Vec<RenderChar>,
+    /// Colors are downgraded to this depth when written
+    depth: ColorDepth,
 }
 
 impl RenderBuffer {
@@ -48,6 +149,8 @@ impl RenderBuffer {
             width: width,
             height: height,
             buffer: vec![RenderChar::empty(); width * height],
+            rendered: Vec::new(),
+            depth: ColorDepth::TrueColor,
         }
     }
 
@@ -57,18 +160,77 @@ impl RenderBuffer {
             width: from.width,
             height: from.height,
             buffer: from.buffer.clone(),
+            rendered: Vec::new(),
+            depth: from.depth,
         }
     }
 
-    /// Put char at specified position.
-    pub fn put(&mut self, x: isize, y: isize, v: RenderChar) {
-        if x >= 0 && y >= 0 {
-            let x = x as usize;
-            let y = y as usize;
-            if x < self.width && y < self.height {
-                self.buffer[x + y * self.width] = v;
-            } else {
+    /// Colors the terminal can show, true color by default
+    pub fn set_color_depth(&mut self, depth: ColorDepth) {
+        self.depth = depth;
+        self.rendered.clear();
+    }
+
+    pub fn color_depth(&self) -> ColorDepth {
+        self.depth
+    }
+
+    // Style as written to terminal
+    fn style(&self, char: &RenderChar) -> Style {
+        char.style.downgrade(self.depth)
+    }
+
+    /// Width of buffer in chars
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Height of buffer in chars
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Put char at specified position. Transparent chars keep what is there.
+    /// Wide glyph covers next cell too, or becomes space at right edge.
+    /// Wide glyph partly overwritten is replaced by spaces
+    pub fn put(&mut self, x: isize, y: isize, mut v: RenderChar) {
+        if x < 0 || y < 0 || x as usize >= self.width || y as usize >= self.height {
+            return;
+        }
+        let (x, y) = (x as usize, y as usize);
+        let i = x + y * self.width;
+        if v.is_continuation() {
+            return;
+        }
+        if v.is_transparent() {
+            if v.style.bg.is_some() {
+                self.buffer[i].style.bg = v.style.bg;
             }
+            return;
+        }
+        let wide = v.width() == 2;
+        if wide && x + 1 == self.width {
+            v = v.with_char(' ');
+        }
+        self.split(x, y);
+        if wide && x + 1 < self.width {
+            self.split(x + 1, y);
+            self.buffer[i + 1] = RenderChar::styled(CONTINUATION, v.style);
+        }
+        self.buffer[i] = v;
+    }
+
+    // Replace wide glyph covering cell, and cell it covers, with spaces
+    fn split(&mut self, x: usize, y: usize) {
+        let i = x + y * self.width;
+        let start = if self.buffer[i].is_continuation() && x > 0 {
+            i - 1
+        } else {
+            i
+        };
+        if self.buffer[start].width() == 2 && start + 1 < (y + 1) * self.width {
+            self.buffer[start] = self.buffer[start].clone().with_char(' ');
+            self.buffer[start + 1] = self.buffer[start].clone();
         }
     }
 
@@ -87,10 +249,34 @@ impl RenderBuffer {
         }
     }
 
-    /// Convert RenderBuffer to printable String.
-    pub fn to_string(&self) -> String {
-        // self.buffer.iter().collect()
-        "no".to_string()
+    /// Rows with color escape codes, ending in newlines. Colors are reset at
+    /// end of every row, so each row can be printed on its own
+    pub fn to_ansi(&self) -> String {
+        let mut out: Vec<u8> = Vec::new();
+        for y in 0..self.height {
+            let mut style = Style::default();
+            let mut run = String::new();
+            for char in self.row(y) {
+                if self.style(char) != style {
+                    flush_run(&mut out, &mut run);
+                    set_style(&mut out, style, self.style(char), self.depth);
+                    style = self.style(char);
+                }
+                char.push_to(&mut run);
+            }
+            flush_run(&mut out, &mut run);
+            if style != Style::default() {
+                out.queue(ResetColor).unwrap();
+            }
+            out.push(b'\n');
+        }
+        String::from_utf8(out).unwrap()
+    }
+
+    // Cells of row that are drawn, skipping ones covered by wide glyphs
+    fn row(&self, y: usize) -> impl Iterator<Item = &RenderChar> {
+        let row = &self.buffer[y * self.width..(y + 1) * self.width];
+        row.iter().filter(|c| !c.is_continuation())
     }
 
     /// Fill every position with specified char
@@ -110,24 +296,96 @@ impl RenderBuffer {
     pub fn resize(&mut self, width: usize, height: usize) {
         self.width = width;
         self.height = height;
-        self.buffer = vec![RenderChar::empty(); self.width * self.height]
+        self.buffer = vec![RenderChar::empty(); self.width * self.height];
+        self.rendered.clear();
     }
 
-    /// Render buffer to given writeable object
-    pub fn render_to<T: std::io::Write>(&self, stdout: &mut T) {
-        let mut colors = Colors {
-            foreground: None,
-            background: None,
-        };
-        for char in &self.buffer {
-            if char.colors != colors {
-                colors = char.colors;
-                stdout.queue(ResetColor).unwrap();
-                stdout.queue(SetColors(colors)).unwrap();
+    /// Forget what is on screen, so next render redraws every cell.
+    /// Needed when something else wrote to the terminal
+    pub fn invalidate(&mut self) {
+        self.rendered.clear();
+    }
+
+    /// Render buffer to given writeable object.
+    /// Only cells changed since previous render are written, in runs
+    /// positioned with cursor moves, so buffer doesn't have to match
+    /// terminal width
+    pub fn render_to<T: std::io::Write>(&mut self, stdout: &mut T) {
+        let full = self.rendered.len() != self.buffer.len();
+        // Terminal style is reset after every render
+        let mut style = Style::default();
+        let mut run = String::new();
+        for y in 0..self.height {
+            // Column where cursor is after last printed char of this row
+            let mut cursor: Option<usize> = None;
+            let mut x = 0;
+            while x < self.width {
+                let i = x + y * self.width;
+                let char = &self.buffer[i];
+                // Wide glyph covers the next cell too
+                let width = if char.is_continuation() {
+                    0
+                } else {
+                    char.width()
+                };
+                if width == 0 || (!full && self.rendered[i] == self.buffer[i]) {
+                    x += width.max(1);
+                    continue;
+                }
+                match cursor {
+                    Some(c) if c == x => {}
+                    // Short gap in same style is cheaper to reprint
+                    Some(c)
+                        if x - c <= MAX_REPRINT_GAP
+                            && self.buffer[i - (x - c)..i]
+                                .iter()
+                                .all(|r| self.style(r) == style && r.width() == 1) =>
+                    {
+                        for r in &self.buffer[i - (x - c)..i] {
+                            r.push_to(&mut run);
+                        }
+                    }
+                    _ => {
+                        flush_run(stdout, &mut run);
+                        stdout.queue(MoveTo(x as u16, y as u16)).unwrap();
+                    }
+                }
+                if self.style(char) != style {
+                    flush_run(stdout, &mut run);
+                    set_style(stdout, style, self.style(char), self.depth);
+                    style = self.style(char);
+                }
+                char.push_to(&mut run);
+                cursor = Some(x + width);
+                x += width;
             }
-            stdout.queue(Print(char.char)).unwrap();
+            flush_run(stdout, &mut run);
         }
-        stdout.queue(ResetColor).unwrap();
+        if style != Style::default() {
+            stdout.queue(ResetColor).unwrap();
+        }
+        self.rendered = self.buffer.clone();
+    }
+}
+
+/// Plain text of buffer without colors, rows ending in newlines
+impl fmt::Display for RenderBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for y in 0..self.height {
+            let mut line = String::new();
+            for c in self.row(y) {
+                c.push_to(&mut line);
+            }
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+fn flush_run<T: std::io::Write>(stdout: &mut T, run: &mut String) {
+    if !run.is_empty() {
+        stdout.queue(Print(&run)).unwrap();
+        run.clear();
     }
 }
 
@@ -218,23 +476,15 @@ impl Drawable for Text {
     fn draw(&self, render: &mut RenderBuffer) {
         let mut cx = self.x;
         let mut cy = self.y;
-        for char in self.text.chars() {
-            if char == '\n' {
+        for grapheme in graphemes(&self.text) {
+            if grapheme == "\n" {
                 cy += 1;
                 cx = self.x;
             } else {
-                render.put(
-                    cx,
-                    cy,
-                    RenderChar::new(
-                        char,
-                        Colors {
-                            foreground: None,
-                            background: None,
-                        },
-                    ),
-                );
-                cx += 1;
+                let c = RenderChar::grapheme(grapheme, Style::default());
+                let width = c.width() as isize;
+                render.put(cx, cy, c);
+                cx += width;
             }
         }
     }
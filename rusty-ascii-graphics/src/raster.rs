@@ -0,0 +1,379 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::time::Duration;
+
+use crossterm::style::{Attribute, Color};
+
+use crate::font::{self, Glyph, CELL_HEIGHT, CELL_WIDTH};
+use crate::style::color_rgb;
+use crate::{RenderBuffer, RenderChar, TRANSPARENT};
+
+type Rgb = (u8, u8, u8);
+
+/// Last frame of animation stays this long before it starts over
+const LAST_FRAME: Duration = Duration::from_secs(1);
+
+/// Rgb pixels, row after row
+#[derive(Clone, PartialEq, Debug)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    /// Three bytes per pixel
+    pub data: Vec<u8>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, color: Rgb) -> Image {
+        Image {
+            width,
+            height,
+            data: [color.0, color.1, color.2].repeat(width * height),
+        }
+    }
+
+    pub fn pixel(&self, x: usize, y: usize) -> Rgb {
+        let i = (x + y * self.width) * 3;
+        (self.data[i], self.data[i + 1], self.data[i + 2])
+    }
+
+    fn fill(&mut self, x: usize, y: usize, size: usize, color: Rgb) {
+        for y in y..(y + size).min(self.height) {
+            for x in x..(x + size).min(self.width) {
+                let i = (x + y * self.width) * 3;
+                self.data[i..i + 3].copy_from_slice(&[color.0, color.1, color.2]);
+            }
+        }
+    }
+
+    pub fn write_png<W: Write>(&self, out: W) -> io::Result<()> {
+        let mut encoder = png::Encoder::new(out, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.data)?;
+        writer.finish()?;
+        Ok(())
+    }
+
+    pub fn save_png<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut file = BufWriter::new(File::create(path)?);
+        self.write_png(&mut file)?;
+        file.flush()
+    }
+}
+
+/// Draws buffers as images with bundled font, every cell taking 8x16 pixels.
+/// Colors are downgraded to depth of buffer, like on terminal
+#[derive(Clone, Copy)]
+pub struct Rasterizer {
+    fg: Rgb,
+    bg: Rgb,
+    scale: usize,
+}
+
+impl Default for Rasterizer {
+    fn default() -> Self {
+        Rasterizer {
+            fg: (229, 229, 229),
+            bg: (0, 0, 0),
+            scale: 1,
+        }
+    }
+}
+
+impl Rasterizer {
+    pub fn new() -> Rasterizer {
+        Rasterizer::default()
+    }
+
+    /// Colors used where terminal would use its default ones
+    pub fn default_colors(mut self, fg: Rgb, bg: Rgb) -> Rasterizer {
+        self.fg = fg;
+        self.bg = bg;
+        self
+    }
+
+    /// Pixels drawn for every pixel of font
+    pub fn scale(mut self, scale: usize) -> Rasterizer {
+        self.scale = scale.max(1);
+        self
+    }
+
+    /// Pixel size of image with given columns and rows of cells
+    pub fn image_size(&self, (columns, rows): (usize, usize)) -> (usize, usize) {
+        (
+            columns * CELL_WIDTH * self.scale,
+            rows * CELL_HEIGHT * self.scale,
+        )
+    }
+
+    pub fn image(&self, buffer: &RenderBuffer) -> Image {
+        self.image_sized(buffer, (buffer.width(), buffer.height()))
+    }
+
+    // Image of given size in cells, buffer is cut or filled with background
+    fn image_sized(&self, buffer: &RenderBuffer, cells: (usize, usize)) -> Image {
+        let (width, height) = self.image_size(cells);
+        let mut image = Image::new(width, height, self.bg);
+        for y in 0..cells.1.min(buffer.height()) {
+            for x in 0..cells.0.min(buffer.width()) {
+                let cell = buffer.get(x as isize, y as isize);
+                if !cell.is_continuation() {
+                    self.draw_cell(&mut image, buffer, &cell, (x, y));
+                }
+            }
+        }
+        image
+    }
+
+    // Foreground and background after attributes
+    fn colors(&self, buffer: &RenderBuffer, cell: &RenderChar) -> (Rgb, Rgb) {
+        let style = buffer.style(cell);
+        let rgb = |color: Option<Color>| color.and_then(color_rgb);
+        let mut fg = rgb(style.fg).unwrap_or(self.fg);
+        let mut bg = rgb(style.bg).unwrap_or(self.bg);
+        if style.has(Attribute::Reverse) {
+            std::mem::swap(&mut fg, &mut bg);
+        }
+        if style.has(Attribute::Dim) {
+            let half = |f: u8, b: u8| ((f as u16 + b as u16) / 2) as u8;
+            fg = (half(fg.0, bg.0), half(fg.1, bg.1), half(fg.2, bg.2));
+        }
+        if style.has(Attribute::Hidden) {
+            fg = bg;
+        }
+        (fg, bg)
+    }
+
+    // Glyphs of every column cell takes, with line attributes added
+    fn glyphs(&self, buffer: &RenderBuffer, cell: &RenderChar) -> Vec<Glyph> {
+        let style = buffer.style(cell);
+        let char = if cell.char == TRANSPARENT {
+            ' '
+        } else {
+            cell.char
+        };
+        let width = cell.width().max(1);
+        let mut glyphs = match font::glyph(char) {
+            Some(glyph) if width == 1 => vec![glyph],
+            _ => font::missing(width),
+        };
+        for glyph in &mut glyphs {
+            if style.has(Attribute::Bold) {
+                for row in glyph.iter_mut() {
+                    *row |= *row << 1;
+                }
+            }
+            if style.has(Attribute::Underlined) {
+                glyph[CELL_HEIGHT - 2] = 0xFF;
+            }
+            if style.has(Attribute::CrossedOut) {
+                glyph[CELL_HEIGHT / 2] = 0xFF;
+            }
+        }
+        glyphs
+    }
+
+    fn draw_cell(
+        &self,
+        image: &mut Image,
+        buffer: &RenderBuffer,
+        cell: &RenderChar,
+        at: (usize, usize),
+    ) {
+        let (fg, bg) = self.colors(buffer, cell);
+        let scale = self.scale;
+        for (i, glyph) in self.glyphs(buffer, cell).iter().enumerate() {
+            let left = (at.0 + i) * CELL_WIDTH * scale;
+            let top = at.1 * CELL_HEIGHT * scale;
+            for (y, row) in glyph.iter().enumerate() {
+                for x in 0..CELL_WIDTH {
+                    let color = if row & 1 << x != 0 { fg } else { bg };
+                    image.fill(left + x * scale, top + y * scale, scale, color);
+                }
+            }
+        }
+    }
+
+    // Every color images of buffer can have
+    fn palette(&self, buffer: &RenderBuffer, palette: &mut HashMap<Rgb, u8>) {
+        for y in 0..buffer.height() {
+            for x in 0..buffer.width() {
+                let (fg, bg) = self.colors(buffer, &buffer.get(x as isize, y as isize));
+                for color in [fg, bg] {
+                    let next = palette.len() as u8;
+                    palette.entry(color).or_insert(next);
+                }
+            }
+        }
+    }
+}
+
+fn gif_error(error: gif::EncodingError) -> io::Error {
+    match error {
+        gif::EncodingError::Io(error) => error,
+        error => io::Error::new(io::ErrorKind::InvalidData, error),
+    }
+}
+
+/// Buffers with times they were shown at, rasterized when written
+/// as animated GIF or PNG. Every frame gets size of first one
+#[derive(Default)]
+pub struct Recording {
+    frames: Vec<(RenderBuffer, Duration)>,
+}
+
+impl Recording {
+    pub fn new() -> Recording {
+        Recording::default()
+    }
+
+    /// Add frame shown `at` given time from start. Frames same as last
+    /// one are skipped, so last one stays on screen longer
+    pub fn push(&mut self, buffer: &RenderBuffer, at: Duration) {
+        if let Some((last, _)) = self.frames.last() {
+            if last.width == buffer.width
+                && last.height == buffer.height
+                && last.buffer == buffer.buffer
+            {
+                return;
+            }
+        }
+        self.frames.push((RenderBuffer::from(buffer), at));
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Cells held in all frames together
+    pub fn cells(&self) -> usize {
+        self.frames
+            .iter()
+            .map(|(buffer, _)| buffer.width() * buffer.height())
+            .sum()
+    }
+
+    /// Time from first to last frame
+    pub fn duration(&self) -> Duration {
+        match (self.frames.first(), self.frames.last()) {
+            (Some((_, first)), Some((_, last))) => *last - *first,
+            _ => Duration::ZERO,
+        }
+    }
+
+    // Size in cells of every frame
+    fn size(&self) -> io::Result<(usize, usize)> {
+        match self.frames.first() {
+            Some((buffer, _)) => Ok((buffer.width(), buffer.height())),
+            None => Err(io::Error::new(io::ErrorKind::InvalidInput, "no frames")),
+        }
+    }
+
+    // Frames with how many units of time each is shown. Times are rounded
+    // from start, so frames shorter than unit get dropped without drifting
+    fn timed(&self, unit: Duration) -> Vec<(&RenderBuffer, u64)> {
+        let start = self.frames.first().map_or(Duration::ZERO, |(_, at)| *at);
+        let ticks =
+            |at: Duration| ((at - start).as_micros() * 2 / unit.as_micros()).div_ceil(2) as u64;
+        let end = ticks(self.frames.last().map_or(start, |(_, at)| *at) + LAST_FRAME);
+        let mut frames = Vec::new();
+        for (i, (buffer, at)) in self.frames.iter().enumerate() {
+            let next = self.frames.get(i + 1).map_or(end, |(_, at)| ticks(*at));
+            if next > ticks(*at) {
+                frames.push((buffer, next - ticks(*at)));
+            }
+        }
+        frames
+    }
+
+    /// Animated GIF, looping forever. Frames are quantized when
+    /// recording has more than 256 colors
+    pub fn write_gif<W: Write>(&self, raster: &Rasterizer, out: W) -> io::Result<()> {
+        let size = self.size()?;
+        let (width, height) = raster.image_size(size);
+        let too_large = || io::Error::new(io::ErrorKind::InvalidInput, "too large for gif");
+        let width = u16::try_from(width).map_err(|_| too_large())?;
+        let height = u16::try_from(height).map_err(|_| too_large())?;
+
+        let frames = self.timed(Duration::from_millis(10));
+        let mut palette = HashMap::from([(raster.bg, 0)]);
+        for (buffer, _) in &frames {
+            raster.palette(buffer, &mut palette);
+            if palette.len() > 256 {
+                break;
+            }
+        }
+        let global = if palette.len() <= 256 {
+            let mut colors = vec![0; palette.len() * 3];
+            for (color, i) in &palette {
+                let i = *i as usize * 3;
+                colors[i..i + 3].copy_from_slice(&[color.0, color.1, color.2]);
+            }
+            colors
+        } else {
+            Vec::new()
+        };
+
+        let mut encoder = gif::Encoder::new(out, width, height, &global).map_err(gif_error)?;
+        encoder
+            .set_repeat(gif::Repeat::Infinite)
+            .map_err(gif_error)?;
+        for (buffer, delay) in frames {
+            let image = raster.image_sized(buffer, size);
+            let mut frame = if global.is_empty() {
+                gif::Frame::from_rgb_speed(width, height, &image.data, 10)
+            } else {
+                let indices = image
+                    .data
+                    .chunks(3)
+                    .map(|c| palette[&(c[0], c[1], c[2])])
+                    .collect::<Vec<u8>>();
+                gif::Frame::from_indexed_pixels(width, height, indices, None)
+            };
+            frame.delay = delay.min(u16::MAX as u64) as u16;
+            encoder.write_frame(&frame).map_err(gif_error)?;
+        }
+        Ok(())
+    }
+
+    /// Animated PNG, looping forever
+    pub fn write_apng<W: Write>(&self, raster: &Rasterizer, out: W) -> io::Result<()> {
+        let size = self.size()?;
+        let (width, height) = raster.image_size(size);
+        let frames = self.timed(Duration::from_millis(1));
+        let mut encoder = png::Encoder::new(out, width as u32, height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.set_animated(frames.len() as u32, 0)?;
+        let mut writer = encoder.write_header()?;
+        for (buffer, delay) in frames {
+            writer.set_frame_delay(delay.min(u16::MAX as u64) as u16, 1000)?;
+            writer.write_image_data(&raster.image_sized(buffer, size).data)?;
+        }
+        writer.finish()?;
+        Ok(())
+    }
+
+    /// Write to file, as APNG when path ends with `.png` or `.apng`,
+    /// GIF otherwise
+    pub fn save<P: AsRef<Path>>(&self, raster: &Rasterizer, path: P) -> io::Result<()> {
+        let apng = path
+            .as_ref()
+            .extension()
+            .is_some_and(|e| e == "png" || e == "apng");
+        let mut file = BufWriter::new(File::create(path)?);
+        if apng {
+            self.write_apng(raster, &mut file)?;
+        } else {
+            self.write_gif(raster, &mut file)?;
+        }
+        file.flush()
+    }
+}
@@ -0,0 +1,176 @@
+use crossterm::style::{Color, Colors};
+
+use crate::{Drawable, RenderBuffer, RenderChar};
+
+/// Colored pixel sampled from a grid. When several pixels fall into one
+/// scaled pixel, color with highest summed weight wins
+#[derive(Clone, Copy)]
+pub struct Sample {
+    pub color: Color,
+    pub weight: u32,
+}
+
+/// Anything that can be downscaled by `Scaled`
+pub trait PixelGrid {
+    /// Width and height in pixels
+    fn size(&self) -> (usize, usize);
+    /// Pixel at position, `None` when empty
+    fn sample(&self, x: usize, y: usize) -> Option<Sample>;
+}
+
+/// Non blank chars are pixels of their foreground color, blank ones of
+/// their background. So any `Drawable` can be drawn to a buffer and scaled
+impl PixelGrid for RenderBuffer {
+    fn size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    fn sample(&self, x: usize, y: usize) -> Option<Sample> {
+        let c = self.get(x as isize, y as isize);
+        let color = if c.char == ' ' {
+            c.style.bg
+        } else {
+            Some(c.style.fg.unwrap_or(Color::Reset))
+        };
+        color.map(|color| Sample { color, weight: 1 })
+    }
+}
+
+/// How scaled pixels are packed into chars
+#[derive(Clone, Copy, PartialEq)]
+pub enum ScaleMode {
+    /// 1x2 pixels per char using `▀` and `▄` with two colors
+    HalfBlock,
+    /// 2x4 dots per char, single color per char
+    Braille,
+}
+
+impl ScaleMode {
+    /// Pixels in one char, horizontally and vertically
+    pub fn pixels_per_char(&self) -> (usize, usize) {
+        match self {
+            ScaleMode::HalfBlock => (1, 2),
+            ScaleMode::Braille => (2, 4),
+        }
+    }
+}
+
+/// Grid scaled to fit given rectangle of chars
+pub struct Scaled<'a, G: PixelGrid> {
+    x: isize,
+    y: isize,
+    width: usize,
+    height: usize,
+    mode: ScaleMode,
+    grid: &'a G,
+}
+
+impl<'a, G: PixelGrid> Scaled<'a, G> {
+    pub fn new(x: isize, y: isize, w: usize, h: usize, mode: ScaleMode, grid: &'a G) -> Self {
+        Scaled {
+            x,
+            y,
+            width: w,
+            height: h,
+            mode,
+            grid,
+        }
+    }
+
+    /// Sample area of grid covered by scaled pixel
+    fn pixel(&self, px: usize, py: usize) -> Option<Color> {
+        let (ppc_x, ppc_y) = self.mode.pixels_per_char();
+        let (pw, ph) = (self.width * ppc_x, self.height * ppc_y);
+        let (gw, gh) = self.grid.size();
+        if pw == 0 || ph == 0 || gw == 0 || gh == 0 {
+            return None;
+        }
+        let (x0, y0) = (px * gw / pw, py * gh / ph);
+        let x1 = ((px + 1) * gw / pw).max(x0 + 1).min(gw);
+        let y1 = ((py + 1) * gh / ph).max(y0 + 1).min(gh);
+
+        let mut totals: Vec<Sample> = Vec::new();
+        for x in x0..x1 {
+            for y in y0..y1 {
+                if let Some(sample) = self.grid.sample(x, y) {
+                    match totals.iter_mut().find(|t| t.color == sample.color) {
+                        Some(total) => total.weight += sample.weight,
+                        None => totals.push(sample),
+                    }
+                }
+            }
+        }
+        totals.iter().max_by_key(|t| t.weight).map(|t| t.color)
+    }
+
+    fn half_block(&self, cx: usize, cy: usize) -> RenderChar {
+        let top = self.pixel(cx, cy * 2);
+        let bottom = self.pixel(cx, cy * 2 + 1);
+        let (char, foreground, background) = match (top, bottom) {
+            (None, None) => (' ', None, None),
+            (Some(t), None) => ('▀', Some(t), None),
+            (None, Some(b)) => ('▄', Some(b), None),
+            (Some(t), Some(b)) if t == b => ('█', Some(t), None),
+            (Some(t), Some(b)) => ('▀', Some(t), Some(b)),
+        };
+        RenderChar::new(
+            char,
+            Colors {
+                foreground,
+                background,
+            },
+        )
+    }
+
+    fn braille(&self, cx: usize, cy: usize) -> RenderChar {
+        // Dot bits in order of (x, y) inside char
+        const DOTS: [(usize, usize, u32); 8] = [
+            (0, 0, 0x01),
+            (0, 1, 0x02),
+            (0, 2, 0x04),
+            (1, 0, 0x08),
+            (1, 1, 0x10),
+            (1, 2, 0x20),
+            (0, 3, 0x40),
+            (1, 3, 0x80),
+        ];
+        let mut bits = 0;
+        let mut counts: Vec<(Color, u32)> = Vec::new();
+        for (dx, dy, bit) in DOTS {
+            if let Some(color) = self.pixel(cx * 2 + dx, cy * 4 + dy) {
+                bits |= bit;
+                match counts.iter_mut().find(|(c, _)| *c == color) {
+                    Some((_, n)) => *n += 1,
+                    None => counts.push((color, 1)),
+                }
+            }
+        }
+        let foreground = counts.iter().max_by_key(|(_, n)| *n).map(|(c, _)| *c);
+        let char = if bits == 0 {
+            ' '
+        } else {
+            char::from_u32(0x2800 + bits).unwrap_or(' ')
+        };
+        RenderChar::new(
+            char,
+            Colors {
+                foreground,
+                background: None,
+            },
+        )
+    }
+}
+
+impl<'a, G: PixelGrid> Drawable for Scaled<'a, G> {
+    fn draw(&self, render: &mut RenderBuffer) {
+        for cx in 0..self.width {
+            for cy in 0..self.height {
+                let c = match self.mode {
+                    ScaleMode::HalfBlock => self.half_block(cx, cy),
+                    ScaleMode::Braille => self.braille(cx, cy),
+                };
+                render.put(self.x + cx as isize, self.y + cy as isize, c);
+            }
+        }
+    }
+}
@@ -0,0 +1,305 @@
+use crossterm::style::Colors;
+
+use crate::{Drawable, RenderBuffer, RenderChar};
+
+/// Set of box drawing glyphs. Glyphs are picked by which neighbours
+/// a cell joins with, so lines meeting in a cell get a proper junction
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BorderStyle {
+    Single,
+    Double,
+    Rounded,
+    Ascii,
+}
+
+impl BorderStyle {
+    /// Join bits, combined with `|`
+    pub const UP: u8 = 1;
+    pub const RIGHT: u8 = 2;
+    pub const DOWN: u8 = 4;
+    pub const LEFT: u8 = 8;
+
+    // Glyph for each combination of join bits
+    fn table(self) -> [char; 16] {
+        match self {
+            BorderStyle::Single => [
+                '·', '│', '─', '└', '│', '│', '┌', '├', '─', '┘', '─', '┴', '┐', '┤', '┬', '┼',
+            ],
+            BorderStyle::Double => [
+                '·', '║', '═', '╚', '║', '║', '╔', '╠', '═', '╝', '═', '╩', '╗', '╣', '╦', '╬',
+            ],
+            BorderStyle::Rounded => [
+                '·', '│', '─', '╰', '│', '│', '╭', '├', '─', '╯', '─', '┴', '╮', '┤', '┬', '┼',
+            ],
+            BorderStyle::Ascii => [
+                '.', '|', '-', '+', '|', '|', '+', '+', '-', '+', '-', '+', '+', '+', '+', '+',
+            ],
+        }
+    }
+
+    /// Glyph joining given neighbours
+    pub fn glyph(self, joins: u8) -> char {
+        self.table()[joins as usize & 15]
+    }
+
+    /// Neighbours glyph of this style joins with, `None` for other chars.
+    /// Lines ending in a cell count as going through it
+    pub fn joins(self, c: char) -> Option<u8> {
+        let table = self.table();
+        (0..16).rev().find(|&i| table[i] == c).map(|i| i as u8)
+    }
+}
+
+/// Joins of every cell in a path with previous and next cell.
+/// Cells that aren't next to each other don't join
+pub fn path_joins(cells: &[(isize, isize)]) -> Vec<u8> {
+    let toward = |from: (isize, isize), to: (isize, isize)| match (to.0 - from.0, to.1 - from.1) {
+        (0, -1) => BorderStyle::UP,
+        (1, 0) => BorderStyle::RIGHT,
+        (0, 1) => BorderStyle::DOWN,
+        (-1, 0) => BorderStyle::LEFT,
+        _ => 0,
+    };
+    (0..cells.len())
+        .map(|i| {
+            let prev = if i > 0 {
+                toward(cells[i], cells[i - 1])
+            } else {
+                0
+            };
+            let next = cells.get(i + 1).map_or(0, |&next| toward(cells[i], next));
+            prev | next
+        })
+        .collect()
+}
+
+// Put box glyph, merging with glyph of same style already in the cell
+fn put_joined(
+    render: &mut RenderBuffer,
+    (x, y): (isize, isize),
+    style: BorderStyle,
+    joins: u8,
+    colors: Colors,
+) {
+    let old = style.joins(render.get(x, y).char).unwrap_or(0);
+    render.put(x, y, RenderChar::new(style.glyph(joins | old), colors));
+}
+
+/// Straight line of one char, Bresenham's algorithm
+pub struct Line {
+    from: (isize, isize),
+    to: (isize, isize),
+    c: RenderChar,
+}
+
+impl Line {
+    pub fn new(from: (isize, isize), to: (isize, isize), c: RenderChar) -> Line {
+        Line { from, to, c }
+    }
+}
+
+impl Drawable for Line {
+    fn draw(&self, render: &mut RenderBuffer) {
+        let ((mut x, mut y), (x1, y1)) = (self.from, self.to);
+        let (dx, dy) = ((x1 - x).abs(), -(y1 - y).abs());
+        let (sx, sy) = ((x1 - x).signum(), (y1 - y).signum());
+        let mut err = dx + dy;
+        loop {
+            render.put(x, y, self.c.clone());
+            if (x, y) == (x1, y1) {
+                break;
+            }
+            let e2 = 2 * err;
+            if e2 >= dy {
+                err += dy;
+                x += sx;
+            }
+            if e2 <= dx {
+                err += dx;
+                y += sy;
+            }
+        }
+    }
+}
+
+/// Rectangle outline of box drawing glyphs, joining with lines it touches
+pub struct BoxShape {
+    x: isize,
+    y: isize,
+    width: isize,
+    height: isize,
+    style: BorderStyle,
+    colors: Colors,
+    fill: Option<RenderChar>,
+}
+
+impl BoxShape {
+    pub fn new(
+        x: isize,
+        y: isize,
+        width: isize,
+        height: isize,
+        style: BorderStyle,
+        colors: Colors,
+    ) -> BoxShape {
+        BoxShape {
+            x,
+            y,
+            width,
+            height,
+            style,
+            colors,
+            fill: None,
+        }
+    }
+
+    /// Fill inside of the box too
+    pub fn fill(mut self, c: RenderChar) -> BoxShape {
+        self.fill = Some(c);
+        self
+    }
+}
+
+impl Drawable for BoxShape {
+    fn draw(&self, render: &mut RenderBuffer) {
+        let (w, h) = (self.width, self.height);
+        if let Some(fill) = &self.fill {
+            for x in 1..w - 1 {
+                for y in 1..h - 1 {
+                    render.put(self.x + x, self.y + y, fill.clone());
+                }
+            }
+        }
+        for x in 0..w {
+            for y in 0..h {
+                let (horizontal, vertical) = (y == 0 || y == h - 1, x == 0 || x == w - 1);
+                let mut joins = 0;
+                if horizontal && x > 0 {
+                    joins |= BorderStyle::LEFT;
+                }
+                if horizontal && x < w - 1 {
+                    joins |= BorderStyle::RIGHT;
+                }
+                if vertical && y > 0 {
+                    joins |= BorderStyle::UP;
+                }
+                if vertical && y < h - 1 {
+                    joins |= BorderStyle::DOWN;
+                }
+                if horizontal || vertical {
+                    let pos = (self.x + x, self.y + y);
+                    put_joined(render, pos, self.style, joins, self.colors);
+                }
+            }
+        }
+    }
+}
+
+/// Ellipse outline or filled ellipse around center cell
+pub struct Ellipse {
+    x: isize,
+    y: isize,
+    rx: isize,
+    ry: isize,
+    c: RenderChar,
+    fill: bool,
+}
+
+impl Ellipse {
+    pub fn new(x: isize, y: isize, rx: isize, ry: isize, c: RenderChar, fill: bool) -> Ellipse {
+        Ellipse {
+            x,
+            y,
+            rx,
+            ry,
+            c,
+            fill,
+        }
+    }
+
+    /// Ellipse that looks round, chars being about twice as tall as wide
+    pub fn circle(x: isize, y: isize, r: isize, c: RenderChar, fill: bool) -> Ellipse {
+        Ellipse::new(x, y, r * 2, r, c, fill)
+    }
+
+    // Half width of row `dy` rows away from center, -1 outside. Cell is
+    // inside when its center is, radii reaching to edges of outermost cells
+    fn half_width(&self, dy: isize) -> isize {
+        if dy.abs() > self.ry {
+            return -1;
+        }
+        let t = dy as f32 / (self.ry as f32 + 0.5);
+        ((self.rx as f32 + 0.5) * (1.0 - t * t).sqrt()).floor() as isize
+    }
+}
+
+impl Drawable for Ellipse {
+    fn draw(&self, render: &mut RenderBuffer) {
+        for dy in -self.ry..=self.ry {
+            let outer = self.half_width(dy);
+            // Outline reaches inward to where next row out ends, so it has no gaps
+            let inner = if self.fill {
+                0
+            } else {
+                (self.half_width(dy.abs() + 1) + 1).min(outer)
+            };
+            for dx in inner..=outer {
+                render.put(self.x + dx, self.y + dy, self.c.clone());
+                render.put(self.x - dx, self.y + dy, self.c.clone());
+            }
+        }
+    }
+}
+
+/// Connected line through points, drawn with box glyphs picked by
+/// direction. Slanted segments become stairs
+pub struct Polyline {
+    cells: Vec<(isize, isize)>,
+    style: BorderStyle,
+    colors: Colors,
+}
+
+// Cells between two points where every step is horizontal or vertical,
+// each step taken toward the exact line
+fn stairs(from: (isize, isize), to: (isize, isize), cells: &mut Vec<(isize, isize)>) {
+    let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+    let distance = |(x, y): (isize, isize)| ((x - from.0) * dy - (y - from.1) * dx).abs();
+    let mut pos = from;
+    cells.push(pos);
+    while pos != to {
+        let step_x = (pos.0 + dx.signum(), pos.1);
+        let step_y = (pos.0, pos.1 + dy.signum());
+        pos = if pos.0 == to.0 {
+            step_y
+        } else if pos.1 == to.1 || distance(step_x) <= distance(step_y) {
+            step_x
+        } else {
+            step_y
+        };
+        cells.push(pos);
+    }
+}
+
+impl Polyline {
+    pub fn new(points: &[(isize, isize)], style: BorderStyle, colors: Colors) -> Polyline {
+        let mut cells = Vec::new();
+        for pair in points.windows(2) {
+            stairs(pair[0], pair[1], &mut cells);
+            cells.pop();
+        }
+        cells.extend(points.last());
+        Polyline {
+            cells,
+            style,
+            colors,
+        }
+    }
+}
+
+impl Drawable for Polyline {
+    fn draw(&self, render: &mut RenderBuffer) {
+        for (&cell, joins) in self.cells.iter().zip(path_joins(&self.cells)) {
+            put_joined(render, cell, self.style, joins, self.colors);
+        }
+    }
+}
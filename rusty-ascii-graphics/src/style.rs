@@ -0,0 +1,327 @@
+use std::env;
+use std::io::Write;
+
+use crossterm::style::{
+    Attribute, Attributes, Color, Colors, ResetColor, SetAttribute, SetAttributes,
+    SetBackgroundColor, SetForegroundColor,
+};
+use crossterm::QueueableCommand;
+
+/// Colors and attributes of a cell. `None` color is terminal default
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Style {
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+    pub attributes: Attributes,
+}
+
+impl Style {
+    pub fn new() -> Style {
+        Style::default()
+    }
+
+    pub fn fg(mut self, color: Color) -> Style {
+        self.fg = Some(color);
+        self
+    }
+
+    pub fn bg(mut self, color: Color) -> Style {
+        self.bg = Some(color);
+        self
+    }
+
+    pub fn attribute(mut self, attribute: Attribute) -> Style {
+        self.attributes.set(attribute);
+        self
+    }
+
+    pub fn bold(self) -> Style {
+        self.attribute(Attribute::Bold)
+    }
+
+    pub fn dim(self) -> Style {
+        self.attribute(Attribute::Dim)
+    }
+
+    pub fn italic(self) -> Style {
+        self.attribute(Attribute::Italic)
+    }
+
+    pub fn underline(self) -> Style {
+        self.attribute(Attribute::Underlined)
+    }
+
+    pub fn reverse(self) -> Style {
+        self.attribute(Attribute::Reverse)
+    }
+
+    pub fn blink(self) -> Style {
+        self.attribute(Attribute::SlowBlink)
+    }
+
+    pub fn has(&self, attribute: Attribute) -> bool {
+        self.attributes.has(attribute)
+    }
+
+    pub fn colors(&self) -> Colors {
+        Colors {
+            foreground: self.fg,
+            background: self.bg,
+        }
+    }
+
+    /// Same style with colors terminal of given depth can show
+    pub fn downgrade(self, depth: ColorDepth) -> Style {
+        Style {
+            fg: self.fg.map(|c| depth.downgrade(c)),
+            bg: self.bg.map(|c| depth.downgrade(c)),
+            attributes: self.attributes,
+        }
+    }
+}
+
+impl From<Colors> for Style {
+    fn from(colors: Colors) -> Style {
+        Style {
+            fg: colors.foreground,
+            bg: colors.background,
+            attributes: Attributes::default(),
+        }
+    }
+}
+
+/// Colors terminal can show
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ColorDepth {
+    /// Named colors only
+    Ansi16,
+    /// Named colors, 6x6x6 cube and grays
+    Ansi256,
+    /// Any rgb color
+    TrueColor,
+}
+
+/// Rgb of named colors in xterm, in ansi number order
+const ANSI16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::DarkRed, (205, 0, 0)),
+    (Color::DarkGreen, (0, 205, 0)),
+    (Color::DarkYellow, (205, 205, 0)),
+    (Color::DarkBlue, (0, 0, 238)),
+    (Color::DarkMagenta, (205, 0, 205)),
+    (Color::DarkCyan, (0, 205, 205)),
+    (Color::Grey, (229, 229, 229)),
+    (Color::DarkGrey, (127, 127, 127)),
+    (Color::Red, (255, 0, 0)),
+    (Color::Green, (0, 255, 0)),
+    (Color::Yellow, (255, 255, 0)),
+    (Color::Blue, (92, 92, 255)),
+    (Color::Magenta, (255, 0, 255)),
+    (Color::Cyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// Channel values of 256 color cube
+const CUBE: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2) as u32;
+    d(a.0, b.0) + d(a.1, b.1) + d(a.2, b.2)
+}
+
+fn ansi256_rgb(n: u8) -> (u8, u8, u8) {
+    match n {
+        0..=15 => ANSI16[n as usize].1,
+        16..=231 => {
+            let n = n - 16;
+            (
+                CUBE[n as usize / 36],
+                CUBE[n as usize / 6 % 6],
+                CUBE[n as usize % 6],
+            )
+        }
+        _ => {
+            let v = 8 + (n - 232) * 10;
+            (v, v, v)
+        }
+    }
+}
+
+// Nearest of cube colors and grays
+fn rgb_to_ansi256(rgb: (u8, u8, u8)) -> u8 {
+    let level = |v: u8| match v {
+        0..=47 => 0,
+        48..=114 => 1,
+        v => (v - 35) / 40,
+    };
+    let cube = 16 + 36 * level(rgb.0) + 6 * level(rgb.1) + level(rgb.2);
+    let average = (rgb.0 as u32 + rgb.1 as u32 + rgb.2 as u32) / 3;
+    let gray = 232 + ((average.saturating_sub(3)) / 10).min(23) as u8;
+    if distance(rgb, ansi256_rgb(gray)) < distance(rgb, ansi256_rgb(cube)) {
+        gray
+    } else {
+        cube
+    }
+}
+
+/// Rgb shown for color, `None` for terminal default
+#[cfg(feature = "export")]
+pub(crate) fn color_rgb(color: Color) -> Option<(u8, u8, u8)> {
+    match color {
+        Color::Reset => None,
+        Color::Rgb { r, g, b } => Some((r, g, b)),
+        Color::AnsiValue(n) => Some(ansi256_rgb(n)),
+        named => ANSI16
+            .iter()
+            .find(|(c, _)| *c == named)
+            .map(|(_, rgb)| *rgb),
+    }
+}
+
+fn rgb_to_ansi16(rgb: (u8, u8, u8)) -> Color {
+    ANSI16
+        .iter()
+        .min_by_key(|(_, named)| distance(rgb, *named))
+        .unwrap()
+        .0
+}
+
+impl ColorDepth {
+    /// Guess from `COLORTERM` and `TERM` environment variables
+    pub fn detect() -> ColorDepth {
+        let colorterm = env::var("COLORTERM").unwrap_or_default();
+        let term = env::var("TERM").unwrap_or_default();
+        if colorterm == "truecolor" || colorterm == "24bit" || term.contains("direct") {
+            ColorDepth::TrueColor
+        } else if term.contains("256") {
+            ColorDepth::Ansi256
+        } else {
+            ColorDepth::Ansi16
+        }
+    }
+
+    /// Nearest color terminal can show
+    pub fn downgrade(self, color: Color) -> Color {
+        match (self, color) {
+            (ColorDepth::TrueColor, color) => color,
+            (ColorDepth::Ansi256, Color::Rgb { r, g, b }) => {
+                Color::AnsiValue(rgb_to_ansi256((r, g, b)))
+            }
+            (ColorDepth::Ansi16, Color::Rgb { r, g, b }) => rgb_to_ansi16((r, g, b)),
+            (ColorDepth::Ansi16, Color::AnsiValue(n)) if n < 16 => ANSI16[n as usize].0,
+            (ColorDepth::Ansi16, Color::AnsiValue(n)) => rgb_to_ansi16(ansi256_rgb(n)),
+            (_, color) => color,
+        }
+    }
+}
+
+// Crossterm writes named colors as 256 color codes, which 16 color terminals
+// don't understand, so those get their own sgr codes
+fn set_color(out: &mut Vec<u8>, color: Color, foreground: bool, depth: ColorDepth) {
+    let named = ANSI16.iter().position(|(c, _)| *c == color);
+    match (depth, named) {
+        (ColorDepth::Ansi16, Some(n)) => {
+            let base = match (foreground, n < 8) {
+                (true, true) => 30,
+                (true, false) => 90 - 8,
+                (false, true) => 40,
+                (false, false) => 100 - 8,
+            };
+            write!(out, "\x1b[{}m", base + n).unwrap();
+        }
+        _ if foreground => {
+            out.queue(SetForegroundColor(color)).unwrap();
+        }
+        _ => {
+            out.queue(SetBackgroundColor(color)).unwrap();
+        }
+    }
+}
+
+/// Attributes turned off together by one code
+const OFF: [(&[Attribute], Attribute); 6] = [
+    (
+        &[Attribute::Bold, Attribute::Dim],
+        Attribute::NormalIntensity,
+    ),
+    (&[Attribute::Italic], Attribute::NoItalic),
+    (
+        &[
+            Attribute::Underlined,
+            Attribute::DoubleUnderlined,
+            Attribute::Undercurled,
+            Attribute::Underdotted,
+            Attribute::Underdashed,
+        ],
+        Attribute::NoUnderline,
+    ),
+    (
+        &[Attribute::SlowBlink, Attribute::RapidBlink],
+        Attribute::NoBlink,
+    ),
+    (&[Attribute::Reverse], Attribute::NoReverse),
+    (&[Attribute::Hidden], Attribute::NoHidden),
+];
+
+fn without(a: Attributes, b: Attributes) -> Attributes {
+    a ^ (a & b)
+}
+
+// Change only what differs. None when some attribute can't be turned off alone
+fn changes(from: Style, to: Style, depth: ColorDepth) -> Option<Vec<u8>> {
+    let mut out = Vec::new();
+    let removed = without(from.attributes, to.attributes);
+    let mut added = without(to.attributes, from.attributes);
+    let mut handled = Attributes::default();
+    for (group, off) in OFF {
+        let group = Attributes::from(group);
+        if !(removed & group).is_empty() {
+            out.queue(SetAttribute(off)).unwrap();
+            // Off code may have turned off attributes that stay
+            added.extend(to.attributes & group);
+        }
+        handled.extend(group);
+    }
+    if !without(removed, handled).is_empty() {
+        return None;
+    }
+    if !added.is_empty() {
+        out.queue(SetAttributes(added)).unwrap();
+    }
+    if to.fg != from.fg {
+        set_color(&mut out, to.fg.unwrap_or(Color::Reset), true, depth);
+    }
+    if to.bg != from.bg {
+        set_color(&mut out, to.bg.unwrap_or(Color::Reset), false, depth);
+    }
+    Some(out)
+}
+
+// Reset everything, then set what target style has
+fn reset_to(to: Style, depth: ColorDepth) -> Vec<u8> {
+    let mut out = Vec::new();
+    out.queue(ResetColor).unwrap();
+    if !to.attributes.is_empty() {
+        out.queue(SetAttributes(to.attributes)).unwrap();
+    }
+    if let Some(color) = to.fg {
+        set_color(&mut out, color, true, depth);
+    }
+    if let Some(color) = to.bg {
+        set_color(&mut out, color, false, depth);
+    }
+    out
+}
+
+/// Write shortest escape codes switching terminal from one style to another
+pub(crate) fn set_style<T: std::io::Write>(out: &mut T, from: Style, to: Style, depth: ColorDepth) {
+    if from == to {
+        return;
+    }
+    let reset = reset_to(to, depth);
+    let codes = match changes(from, to, depth) {
+        Some(changes) if changes.len() <= reset.len() => changes,
+        _ => reset,
+    };
+    out.write_all(&codes).unwrap();
+}
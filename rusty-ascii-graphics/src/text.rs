@@ -0,0 +1,217 @@
+use crossterm::style::{Attribute, Color, Colors};
+
+use crate::{grapheme_width, graphemes, str_width, Drawable, RenderBuffer, RenderChar, Style};
+
+/// Piece of text drawn in one style
+#[derive(Clone)]
+pub struct Span {
+    pub text: String,
+    pub style: Style,
+}
+
+impl Span {
+    /// Text in terminal default style
+    pub fn new(text: &str) -> Span {
+        Span {
+            text: text.to_string(),
+            style: Style::default(),
+        }
+    }
+
+    pub fn style(mut self, style: Style) -> Span {
+        self.style = style;
+        self
+    }
+
+    pub fn colors(mut self, colors: Colors) -> Span {
+        self.style.fg = colors.foreground;
+        self.style.bg = colors.background;
+        self
+    }
+
+    pub fn fg(mut self, color: Color) -> Span {
+        self.style = self.style.fg(color);
+        self
+    }
+
+    pub fn bg(mut self, color: Color) -> Span {
+        self.style = self.style.bg(color);
+        self
+    }
+
+    pub fn attribute(mut self, attribute: Attribute) -> Span {
+        self.style = self.style.attribute(attribute);
+        self
+    }
+
+    pub fn bold(self) -> Span {
+        self.attribute(Attribute::Bold)
+    }
+
+    pub fn underline(self) -> Span {
+        self.attribute(Attribute::Underlined)
+    }
+}
+
+/// Horizontal placement of lines inside text box
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+// Laid out grapheme and index of span it came from
+type Cell<'a> = (&'a str, usize);
+
+fn line_width(line: &[Cell]) -> usize {
+    line.iter().map(|(g, _)| grapheme_width(g)).sum()
+}
+
+fn trim_end(line: &mut Vec<Cell>) {
+    while line.last().is_some_and(|(g, _)| *g == " ") {
+        line.pop();
+    }
+}
+
+// Greedy word wrap, words longer than line are broken
+fn wrap_line<'a>(line: &[Cell<'a>], width: usize) -> Vec<Vec<Cell<'a>>> {
+    let mut lines = Vec::new();
+    let mut current: Vec<Cell> = Vec::new();
+    for chunk in line.split_inclusive(|(g, _)| *g == " ") {
+        let word_len = chunk
+            .iter()
+            .rposition(|(g, _)| *g != " ")
+            .map_or(0, |i| i + 1);
+        let (word, spaces) = chunk.split_at(word_len);
+        if line_width(&current) + line_width(word) > width && !current.is_empty() {
+            trim_end(&mut current);
+            lines.push(std::mem::take(&mut current));
+        }
+        for &cell in word {
+            // Line always takes at least one grapheme, even too wide one
+            if line_width(&current) + grapheme_width(cell.0) > width && !current.is_empty() {
+                lines.push(std::mem::take(&mut current));
+            }
+            current.push(cell);
+        }
+        // Spaces are dropped at start of wrapped line
+        if !current.is_empty() || lines.is_empty() {
+            current.extend_from_slice(spaces);
+        }
+    }
+    trim_end(&mut current);
+    lines.push(current);
+    lines
+}
+
+/// Spans laid out in a box. Lines break at `\n` and, with wrapping on,
+/// between words. Text that doesn't fit is cut, ending with ellipsis if set
+pub struct StyledText {
+    x: isize,
+    y: isize,
+    width: usize,
+    height: usize,
+    spans: Vec<Span>,
+    align: Align,
+    wrap: bool,
+    ellipsis: Option<String>,
+}
+
+impl StyledText {
+    pub fn new(x: isize, y: isize, width: usize, height: usize, spans: Vec<Span>) -> StyledText {
+        StyledText {
+            x,
+            y,
+            width,
+            height,
+            spans,
+            align: Align::Left,
+            wrap: false,
+            ellipsis: None,
+        }
+    }
+
+    pub fn align(mut self, align: Align) -> StyledText {
+        self.align = align;
+        self
+    }
+
+    pub fn wrap(mut self, wrap: bool) -> StyledText {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Mark put at the end of cut text, like `…` or `...`
+    pub fn ellipsis(mut self, mark: &str) -> StyledText {
+        self.ellipsis = Some(mark.to_string());
+        self
+    }
+
+    /// Lines text takes with wrapping, before cutting to height
+    pub fn line_count(&self) -> usize {
+        self.layout().len()
+    }
+
+    fn layout(&self) -> Vec<Vec<Cell<'_>>> {
+        let mut paragraphs = vec![Vec::new()];
+        for (i, span) in self.spans.iter().enumerate() {
+            for g in graphemes(&span.text) {
+                match g {
+                    "\n" => paragraphs.push(Vec::new()),
+                    g => paragraphs.last_mut().unwrap().push((g, i)),
+                }
+            }
+        }
+        if !self.wrap {
+            return paragraphs;
+        }
+        paragraphs
+            .iter()
+            .flat_map(|p| wrap_line(p, self.width))
+            .collect()
+    }
+
+    // Shorten line to box width, making room for ellipsis
+    fn cut<'a>(&'a self, line: &mut Vec<Cell<'a>>) {
+        let mark = self.ellipsis.as_deref().unwrap_or("");
+        let span = line.last().map_or(0, |(_, span)| *span);
+        while !line.is_empty() && line_width(line) + str_width(mark) > self.width {
+            line.pop();
+        }
+        trim_end(line);
+        if str_width(mark) <= self.width {
+            line.extend(graphemes(mark).map(|g| (g, span)));
+        }
+    }
+}
+
+impl Drawable for StyledText {
+    fn draw(&self, render: &mut RenderBuffer) {
+        let mut lines = self.layout();
+        let overflow = lines.len() > self.height;
+        lines.truncate(self.height);
+        let last = lines.len().saturating_sub(1);
+        for (row, line) in lines.iter_mut().enumerate() {
+            if line_width(line) > self.width || (overflow && row == last) {
+                self.cut(line);
+            }
+            let free = self.width.saturating_sub(line_width(line));
+            let mut x = self.x
+                + match self.align {
+                    Align::Left => 0,
+                    Align::Center => free / 2,
+                    Align::Right => free,
+                } as isize;
+            for &(g, span) in line.iter() {
+                let span = &self.spans[span];
+                render.put(
+                    x,
+                    self.y + row as isize,
+                    RenderChar::grapheme(g, span.style),
+                );
+                x += grapheme_width(g) as isize;
+            }
+        }
+    }
+}
@@ -0,0 +1,632 @@
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEventKind};
+
+use crate::{
+    grapheme_width, graphemes, str_width, Drawable, Rect, RenderBuffer, RenderChar, Span, Style,
+    StyledText,
+};
+
+/// What widget did with event
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Response {
+    /// Event isn't used by widget
+    Ignored,
+    /// Only looks of widget changed, like cursor or scroll position
+    Handled,
+    /// Value changed
+    Changed,
+    /// Confirmed with Enter or click
+    Activated,
+}
+
+/// Interactive part of user interface. Widgets keep their state between
+/// frames and are drawn like any other `Drawable`
+pub trait Widget: Drawable {
+    /// Area taking mouse events
+    fn rect(&self) -> Rect;
+    /// Whether widget can take keys. Widgets only showing something can't
+    fn focusable(&self) -> bool {
+        true
+    }
+    fn set_focus(&mut self, focused: bool);
+    fn handle_key(&mut self, _event: &KeyEvent) -> Response {
+        Response::Ignored
+    }
+    /// Mouse event at position inside widget rect
+    fn handle_mouse(&mut self, _kind: MouseEventKind, _x: isize, _y: isize) -> Response {
+        Response::Ignored
+    }
+}
+
+// Spaces in style over whole rect, so background covers it
+fn fill(render: &mut RenderBuffer, rect: Rect, style: Style) {
+    for y in 0..rect.height as isize {
+        for x in 0..rect.width as isize {
+            render.put(rect.x + x, rect.y + y, RenderChar::styled(' ', style));
+        }
+    }
+}
+
+/// Single line text input with cursor. Long text scrolls to keep
+/// cursor visible
+#[derive(Clone)]
+pub struct TextInput {
+    pub rect: Rect,
+    value: String,
+    /// Cursor position in graphemes
+    cursor: usize,
+    /// First visible grapheme
+    offset: usize,
+    /// Most graphemes value can have
+    max_len: Option<usize>,
+    focused: bool,
+    style: Style,
+    cursor_style: Style,
+}
+
+impl TextInput {
+    pub fn new(x: isize, y: isize, width: usize) -> TextInput {
+        TextInput {
+            rect: Rect::new(x, y, width, 1),
+            value: String::new(),
+            cursor: 0,
+            offset: 0,
+            max_len: None,
+            focused: false,
+            style: Style::default(),
+            cursor_style: Style::new().reverse(),
+        }
+    }
+
+    pub fn style(mut self, style: Style) -> TextInput {
+        self.style = style;
+        self
+    }
+
+    pub fn cursor_style(mut self, style: Style) -> TextInput {
+        self.cursor_style = style;
+        self
+    }
+
+    pub fn max_len(mut self, max_len: usize) -> TextInput {
+        self.max_len = Some(max_len);
+        self
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Replace text, moving cursor to its end
+    pub fn set_value(&mut self, value: &str) {
+        self.value = value.to_string();
+        self.cursor = graphemes(&self.value).count();
+        self.offset = self.visible_from();
+    }
+
+    /// Cursor position in graphemes
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    // Byte index of grapheme, or end of text
+    fn byte_index(&self, grapheme: usize) -> usize {
+        graphemes(&self.value).take(grapheme).map(str::len).sum()
+    }
+
+    fn insert(&mut self, c: char) -> Response {
+        let len = graphemes(&self.value).count();
+        if self.max_len.is_some_and(|max| len >= max) {
+            return Response::Handled;
+        }
+        let index = self.byte_index(self.cursor);
+        self.value.insert(index, c);
+        // Combining char joins grapheme before cursor
+        self.cursor = graphemes(&self.value[..index + c.len_utf8()]).count();
+        Response::Changed
+    }
+
+    // Remove grapheme at index
+    fn remove(&mut self, grapheme: usize) -> Response {
+        let (start, end) = (self.byte_index(grapheme), self.byte_index(grapheme + 1));
+        if start == end {
+            return Response::Handled;
+        }
+        self.value.replace_range(start..end, "");
+        Response::Changed
+    }
+
+    // First visible grapheme keeping cursor on screen, scrolling
+    // as little as possible from current offset. Cursor cell after
+    // end of text takes a column too
+    fn visible_from(&self) -> usize {
+        let widths: Vec<usize> = graphemes(&self.value).map(grapheme_width).collect();
+        let width = |range: &[usize]| range.iter().sum::<usize>() + 1;
+        let mut offset = self.offset.min(self.cursor);
+        while offset < self.cursor && width(&widths[offset..self.cursor]) > self.rect.width {
+            offset += 1;
+        }
+        // No empty space at the end while earlier text is hidden
+        while offset > 0 && width(&widths[offset - 1..]) <= self.rect.width {
+            offset -= 1;
+        }
+        offset
+    }
+}
+
+impl Drawable for TextInput {
+    fn draw(&self, render: &mut RenderBuffer) {
+        fill(render, self.rect, self.style);
+        let (y, end) = (self.rect.y, self.rect.x + self.rect.width as isize);
+        let mut x = self.rect.x;
+        let visible = graphemes(&self.value).chain([" "]).enumerate();
+        for (i, g) in visible.skip(self.visible_from()) {
+            let width = grapheme_width(g) as isize;
+            if x + width > end {
+                break;
+            }
+            let style = if self.focused && i == self.cursor {
+                self.cursor_style
+            } else {
+                self.style
+            };
+            render.put(x, y, RenderChar::grapheme(g, style));
+            x += width;
+        }
+    }
+}
+
+impl Widget for TextInput {
+    fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    fn set_focus(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    fn handle_key(&mut self, event: &KeyEvent) -> Response {
+        let len = graphemes(&self.value).count();
+        let response = match event.code {
+            KeyCode::Char(c)
+                if !event
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                self.insert(c)
+            }
+            KeyCode::Backspace if self.cursor > 0 => {
+                self.cursor -= 1;
+                self.remove(self.cursor)
+            }
+            KeyCode::Delete => self.remove(self.cursor),
+            KeyCode::Left => {
+                self.cursor = self.cursor.saturating_sub(1);
+                Response::Handled
+            }
+            KeyCode::Right => {
+                self.cursor = (self.cursor + 1).min(len);
+                Response::Handled
+            }
+            KeyCode::Home => {
+                self.cursor = 0;
+                Response::Handled
+            }
+            KeyCode::End => {
+                self.cursor = len;
+                Response::Handled
+            }
+            KeyCode::Backspace => Response::Handled,
+            KeyCode::Enter => Response::Activated,
+            _ => Response::Ignored,
+        };
+        self.offset = self.visible_from();
+        response
+    }
+
+    fn handle_mouse(&mut self, kind: MouseEventKind, x: isize, _y: isize) -> Response {
+        if kind != MouseEventKind::Down(MouseButton::Left) {
+            return Response::Ignored;
+        }
+        // Grapheme under pointer, or end of text
+        let offset = self.visible_from();
+        let mut column = 0;
+        let mut cursor = offset;
+        for g in graphemes(&self.value).skip(offset) {
+            column += grapheme_width(g) as isize;
+            if column > x {
+                break;
+            }
+            cursor += 1;
+        }
+        self.cursor = cursor;
+        Response::Handled
+    }
+}
+
+/// Scrolling list of lines with one selected
+#[derive(Clone)]
+pub struct ListView {
+    pub rect: Rect,
+    items: Vec<String>,
+    selected: usize,
+    /// First visible item
+    offset: usize,
+    focused: bool,
+    style: Style,
+    highlight: Style,
+}
+
+impl ListView {
+    pub fn new(rect: Rect, items: Vec<String>) -> ListView {
+        ListView {
+            rect,
+            items,
+            selected: 0,
+            offset: 0,
+            focused: false,
+            style: Style::default(),
+            highlight: Style::new().reverse(),
+        }
+    }
+
+    pub fn style(mut self, style: Style) -> ListView {
+        self.style = style;
+        self
+    }
+
+    /// Style of selected item while list has focus
+    pub fn highlight(mut self, style: Style) -> ListView {
+        self.highlight = style;
+        self
+    }
+
+    pub fn items(&self) -> &[String] {
+        &self.items
+    }
+
+    /// Replace items, keeping selection when it's still in list
+    pub fn set_items(&mut self, items: Vec<String>) {
+        self.items = items;
+        self.select(self.selected);
+    }
+
+    /// Selected item, `None` when list is empty
+    pub fn selected(&self) -> Option<usize> {
+        (!self.items.is_empty()).then_some(self.selected)
+    }
+
+    /// Select item and scroll to it
+    pub fn select(&mut self, index: usize) {
+        self.selected = index.min(self.items.len().saturating_sub(1));
+        let rows = self.rect.height.max(1);
+        if self.selected < self.offset {
+            self.offset = self.selected;
+        } else if self.selected >= self.offset + rows {
+            self.offset = self.selected + 1 - rows;
+        }
+    }
+
+    // Select item moved to, `Ignored` when already at that end of list
+    fn move_to(&mut self, index: usize) -> Response {
+        let index = index.min(self.items.len().saturating_sub(1));
+        if index == self.selected {
+            return Response::Ignored;
+        }
+        self.select(index);
+        Response::Changed
+    }
+
+    // Largest offset still filling the list
+    fn max_offset(&self) -> usize {
+        self.items.len().saturating_sub(self.rect.height)
+    }
+}
+
+impl Drawable for ListView {
+    fn draw(&self, render: &mut RenderBuffer) {
+        fill(render, self.rect, self.style);
+        let visible = self.items.iter().enumerate().skip(self.offset);
+        for (row, (i, item)) in visible.take(self.rect.height).enumerate() {
+            let style = match (i == self.selected, self.focused) {
+                (true, true) => self.highlight,
+                (true, false) => self.style.bold(),
+                _ => self.style,
+            };
+            let rect = Rect::new(self.rect.x, self.rect.y + row as isize, self.rect.width, 1);
+            fill(render, rect, style);
+            let text = vec![Span::new(item).style(style)];
+            render.draw(&StyledText::new(rect.x, rect.y, rect.width, 1, text).ellipsis("…"));
+        }
+    }
+}
+
+impl Widget for ListView {
+    fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    fn set_focus(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    fn handle_key(&mut self, event: &KeyEvent) -> Response {
+        let page = self.rect.height.max(1);
+        match event.code {
+            KeyCode::Up if self.selected > 0 => self.move_to(self.selected - 1),
+            KeyCode::Down => self.move_to(self.selected + 1),
+            KeyCode::PageUp => self.move_to(self.selected.saturating_sub(page)),
+            KeyCode::PageDown => self.move_to(self.selected + page),
+            KeyCode::Home => self.move_to(0),
+            KeyCode::End => self.move_to(self.items.len()),
+            KeyCode::Enter if !self.items.is_empty() => Response::Activated,
+            _ => Response::Ignored,
+        }
+    }
+
+    fn handle_mouse(&mut self, kind: MouseEventKind, _x: isize, y: isize) -> Response {
+        match kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let index = self.offset + y as usize;
+                if index >= self.items.len() {
+                    Response::Handled
+                } else if index == self.selected {
+                    Response::Activated
+                } else {
+                    self.select(index);
+                    Response::Changed
+                }
+            }
+            MouseEventKind::ScrollUp => {
+                self.offset = self.offset.saturating_sub(1);
+                Response::Handled
+            }
+            MouseEventKind::ScrollDown => {
+                self.offset = (self.offset + 1).min(self.max_offset());
+                Response::Handled
+            }
+            _ => Response::Ignored,
+        }
+    }
+}
+
+/// Label in brackets, activated with Enter, space or click
+#[derive(Clone)]
+pub struct Button {
+    pub rect: Rect,
+    label: String,
+    focused: bool,
+    style: Style,
+    focus_style: Style,
+}
+
+impl Button {
+    pub fn new(x: isize, y: isize, label: &str) -> Button {
+        Button {
+            rect: Rect::new(x, y, str_width(label) + 4, 1),
+            label: label.to_string(),
+            focused: false,
+            style: Style::default(),
+            focus_style: Style::new().reverse(),
+        }
+    }
+
+    pub fn style(mut self, style: Style) -> Button {
+        self.style = style;
+        self
+    }
+
+    pub fn focus_style(mut self, style: Style) -> Button {
+        self.focus_style = style;
+        self
+    }
+}
+
+impl Drawable for Button {
+    fn draw(&self, render: &mut RenderBuffer) {
+        let style = if self.focused {
+            self.focus_style
+        } else {
+            self.style
+        };
+        let text = vec![Span::new(&format!("[ {} ]", self.label)).style(style)];
+        let rect = self.rect;
+        render.draw(&StyledText::new(rect.x, rect.y, rect.width, 1, text));
+    }
+}
+
+impl Widget for Button {
+    fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    fn set_focus(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    fn handle_key(&mut self, event: &KeyEvent) -> Response {
+        match event.code {
+            KeyCode::Enter | KeyCode::Char(' ') => Response::Activated,
+            _ => Response::Ignored,
+        }
+    }
+
+    fn handle_mouse(&mut self, kind: MouseEventKind, _x: isize, _y: isize) -> Response {
+        match kind {
+            MouseEventKind::Down(MouseButton::Left) => Response::Activated,
+            _ => Response::Ignored,
+        }
+    }
+}
+
+/// Label with `[x]` box, flipped with Enter, space or click
+#[derive(Clone)]
+pub struct Checkbox {
+    pub rect: Rect,
+    label: String,
+    checked: bool,
+    focused: bool,
+    style: Style,
+    focus_style: Style,
+}
+
+impl Checkbox {
+    pub fn new(x: isize, y: isize, label: &str, checked: bool) -> Checkbox {
+        Checkbox {
+            rect: Rect::new(x, y, str_width(label) + 4, 1),
+            label: label.to_string(),
+            checked,
+            focused: false,
+            style: Style::default(),
+            focus_style: Style::new().reverse(),
+        }
+    }
+
+    pub fn style(mut self, style: Style) -> Checkbox {
+        self.style = style;
+        self
+    }
+
+    pub fn focus_style(mut self, style: Style) -> Checkbox {
+        self.focus_style = style;
+        self
+    }
+
+    pub fn checked(&self) -> bool {
+        self.checked
+    }
+
+    pub fn set_checked(&mut self, checked: bool) {
+        self.checked = checked;
+    }
+
+    fn flip(&mut self) -> Response {
+        self.checked = !self.checked;
+        Response::Changed
+    }
+}
+
+impl Drawable for Checkbox {
+    fn draw(&self, render: &mut RenderBuffer) {
+        let style = if self.focused {
+            self.focus_style
+        } else {
+            self.style
+        };
+        let mark = if self.checked { 'x' } else { ' ' };
+        let text = vec![Span::new(&format!("[{}] {}", mark, self.label)).style(style)];
+        let rect = self.rect;
+        render.draw(&StyledText::new(rect.x, rect.y, rect.width, 1, text));
+    }
+}
+
+impl Widget for Checkbox {
+    fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    fn set_focus(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    fn handle_key(&mut self, event: &KeyEvent) -> Response {
+        match event.code {
+            KeyCode::Enter | KeyCode::Char(' ') => self.flip(),
+            _ => Response::Ignored,
+        }
+    }
+
+    fn handle_mouse(&mut self, kind: MouseEventKind, _x: isize, _y: isize) -> Response {
+        match kind {
+            MouseEventKind::Down(MouseButton::Left) => self.flip(),
+            _ => Response::Ignored,
+        }
+    }
+}
+
+/// Which widget of a form takes keys. Keys go to focused widget, and
+/// the ones it ignores move focus: Tab and Down forward, Shift+Tab and Up
+/// back. Mouse events go to widget under pointer, clicks focus it.
+/// Widgets are owned by caller and passed in every time, in focus order
+#[derive(Default)]
+pub struct Focus {
+    index: Option<usize>,
+}
+
+impl Focus {
+    pub fn new() -> Focus {
+        Focus { index: None }
+    }
+
+    /// Index of focused widget
+    pub fn focused(&self) -> Option<usize> {
+        self.index
+    }
+
+    /// Focus widget, unfocusing others
+    pub fn set(&mut self, index: usize, widgets: &mut [&mut dyn Widget]) {
+        self.index = Some(index);
+        for (i, widget) in widgets.iter_mut().enumerate() {
+            widget.set_focus(i == index);
+        }
+    }
+
+    // Focus next focusable widget in given direction, wrapping around
+    fn step(&mut self, widgets: &mut [&mut dyn Widget], forward: bool) -> Option<usize> {
+        let len = widgets.len();
+        let start = match (self.index, forward) {
+            (Some(i), true) => i + 1,
+            (Some(i), false) => i + len - 1,
+            (None, _) => 0,
+        };
+        let index = (0..len)
+            .map(|n| {
+                if forward {
+                    (start + n) % len
+                } else {
+                    (start + len - n) % len
+                }
+            })
+            .find(|&i| widgets[i].focusable())?;
+        self.set(index, widgets);
+        Some(index)
+    }
+
+    /// Route event to widgets. Gives index of widget that got it
+    /// and what it did, `None` when no widget used it
+    pub fn handle(
+        &mut self,
+        event: &Event,
+        widgets: &mut [&mut dyn Widget],
+    ) -> Option<(usize, Response)> {
+        match event {
+            Event::Key(key) => {
+                let index = match self.index.filter(|&i| i < widgets.len()) {
+                    Some(index) => index,
+                    None => self.step(widgets, true)?,
+                };
+                let response = widgets[index].handle_key(key);
+                if response != Response::Ignored {
+                    return Some((index, response));
+                }
+                let forward = match key.code {
+                    KeyCode::Tab | KeyCode::Down => true,
+                    KeyCode::BackTab | KeyCode::Up => false,
+                    _ => return None,
+                };
+                self.step(widgets, forward)
+                    .map(|index| (index, Response::Handled))
+            }
+            Event::Mouse(mouse) => {
+                let (x, y) = (mouse.column as isize, mouse.row as isize);
+                let index = widgets.iter().rposition(|w| w.rect().contains(x, y))?;
+                if matches!(mouse.kind, MouseEventKind::Down(_)) && widgets[index].focusable() {
+                    self.set(index, widgets);
+                }
+                let rect = widgets[index].rect();
+                let response = widgets[index].handle_mouse(mouse.kind, x - rect.x, y - rect.y);
+                Some((index, response))
+            }
+            _ => None,
+        }
+    }
+}
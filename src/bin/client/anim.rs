@@ -0,0 +1,145 @@
+use std::time::{Duration, Instant};
+
+use rusty_snake::board::Effects;
+use rusty_snake::{Game, Snake};
+
+/// Default frames per second of render loop, independent of tick rate
+pub const DEFAULT_FRAME_RATE: u32 = 30;
+
+const DEATH_FLASH: Duration = Duration::from_millis(600);
+const FLASH_PERIOD: u128 = 100;
+const PULSE_PERIOD: u128 = 800;
+
+/// Body of snake that disappeared, flashed for a moment
+pub struct Death {
+    pub body: Vec<(isize, isize)>,
+    pub color: u8,
+    at: Instant,
+}
+
+/// Keeps previous snapshot to draw motion between ticks
+pub struct Animation {
+    prev: Option<Game>,
+    received: Instant,
+    started: Instant,
+    deaths: Vec<Death>,
+}
+
+// Step from one cell to a neighbour, shortest way around on wrapping maps
+fn step(from: (isize, isize), to: (isize, isize), size: (usize, usize)) -> (isize, isize) {
+    let wrap = |d: isize, size: usize| {
+        let size = size as isize;
+        if d > size / 2 {
+            d - size
+        } else if d < -size / 2 {
+            d + size
+        } else {
+            d
+        }
+    };
+    (
+        wrap(to.0 - from.0, size.0).signum(),
+        wrap(to.1 - from.1, size.1).signum(),
+    )
+}
+
+// Half block covering side of cell in given direction
+fn half_toward(dir: (isize, isize)) -> Option<char> {
+    match dir {
+        (1, 0) => Some('▐'),
+        (-1, 0) => Some('▌'),
+        (0, 1) => Some('▄'),
+        (0, -1) => Some('▀'),
+        _ => None,
+    }
+}
+
+impl Animation {
+    pub fn new() -> Self {
+        Self {
+            prev: None,
+            received: Instant::now(),
+            started: Instant::now(),
+            deaths: Vec::new(),
+        }
+    }
+
+    /// Remember replaced snapshot when a new one arrives
+    pub fn push(&mut self, prev: Game, game: &Game) {
+        for snake in &prev.snakes {
+            if !game.snakes.iter().any(|s| s.name == snake.name) {
+                self.deaths.push(Death {
+                    body: snake.body.clone(),
+                    color: snake.color,
+                    at: Instant::now(),
+                });
+            }
+        }
+        self.prev = Some(prev);
+        self.received = Instant::now();
+    }
+
+    /// Part of tick passed since last snapshot, from 0 to 1
+    pub fn progress(&self, game: &Game) -> f32 {
+        let period = game.tick_period().as_secs_f32();
+        (self.received.elapsed().as_secs_f32() / period).min(1.0)
+    }
+
+    fn prev_snake(&self, snake: &Snake) -> Option<&Snake> {
+        self.prev
+            .as_ref()?
+            .snakes
+            .iter()
+            .find(|s| s.name == snake.name)
+    }
+
+    /// Forget snakes that finished flashing
+    pub fn prune(&mut self) {
+        self.deaths.retain(|d| d.at.elapsed() < DEATH_FLASH);
+    }
+}
+
+impl Effects for Animation {
+    /// Head glyph while it's entering its cell: half block on the side it came from
+    fn head(&self, game: &Game, snake: &Snake) -> Option<char> {
+        let prev = self.prev_snake(snake)?;
+        if prev.head == snake.head || self.progress(game) >= 0.5 {
+            return None;
+        }
+        let dir = step(snake.head, prev.head, game.size);
+        half_toward(dir)
+    }
+
+    /// Cell tail just left and glyph of its remaining half, while leaving it
+    fn tail(&self, game: &Game, snake: &Snake) -> Option<((isize, isize), char)> {
+        let prev = self.prev_snake(snake)?;
+        let old = *prev.body.last()?;
+        if snake.body.contains(&old) || self.progress(game) >= 0.5 {
+            return None;
+        }
+        let new = *snake.body.last()?;
+        let glyph = half_toward(step(old, new, game.size))?;
+        Some((old, glyph))
+    }
+
+    /// Food glyph, shrinking shortly once per pulse. Phase depends on position
+    /// so not all food pulses at once
+    fn food(&self, pos: (isize, isize), glyph: char, small: char) -> char {
+        let offset = (pos.0 * 7 + pos.1 * 13).unsigned_abs() as u128 * 97;
+        let phase = (self.started.elapsed().as_millis() + offset) % PULSE_PERIOD;
+        if phase < PULSE_PERIOD / 4 {
+            small
+        } else {
+            glyph
+        }
+    }
+
+    /// Snakes that disappeared recently and are visible in this frame
+    fn flashes(&self) -> Vec<(&[(isize, isize)], u8)> {
+        self.deaths
+            .iter()
+            .filter(|d| (d.at.elapsed().as_millis() / FLASH_PERIOD).is_multiple_of(2))
+            .map(|d| (&d.body[..], d.color))
+            .collect()
+    }
+}
@@ -0,0 +1,128 @@
+use std::mem::size_of;
+use std::path::PathBuf;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use rusty_ascii_graphics::{Rasterizer, Recording, RenderBuffer, RenderChar};
+
+/// Recording stops by itself after this long
+const MAX_RECORDING: Duration = Duration::from_secs(120);
+/// Recording also stops once its frames take this much memory
+const MAX_RECORDING_BYTES: usize = 64 * 1024 * 1024;
+/// Recordings keep at most one frame this often, 10 per second
+const FRAME_TIME: Duration = Duration::from_millis(100);
+
+struct Running {
+    recording: Recording,
+    started: Instant,
+    /// Time from start when next frame is kept
+    next: Duration,
+}
+
+/// Screenshots and recordings of game screen. Files are written on their
+/// own thread, results come back as messages to show in chat
+pub struct Capture {
+    dir: PathBuf,
+    /// Extension of recordings, `gif` or `png` for animated PNG
+    format: String,
+    raster: Rasterizer,
+    recording: Option<Running>,
+    results_tx: Sender<String>,
+    results: Receiver<String>,
+}
+
+impl Capture {
+    pub fn new(dir: PathBuf, format: &str) -> Capture {
+        let (results_tx, results) = channel();
+        Capture {
+            dir,
+            format: format.to_string(),
+            raster: Rasterizer::new(),
+            recording: None,
+            results_tx,
+            results,
+        }
+    }
+
+    // New file in capture directory, named after current time
+    fn path(&self, extension: &str) -> PathBuf {
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_millis());
+        self.dir
+            .join(format!("rusty-snake-{}.{}", millis, extension))
+    }
+
+    // Run save on its own thread, reporting where file went
+    fn save<F>(&self, path: PathBuf, save: F)
+    where
+        F: FnOnce(&PathBuf) -> std::io::Result<()> + Send + 'static,
+    {
+        let results = self.results_tx.clone();
+        thread::spawn(move || {
+            let result = match save(&path) {
+                Ok(()) => format!("Saved {}", path.display()),
+                Err(e) => format!("Couldn't save {}: {}", path.display(), e),
+            };
+            results.send(result).ok();
+        });
+    }
+
+    pub fn screenshot(&self, buffer: &RenderBuffer) {
+        let buffer = RenderBuffer::from(buffer);
+        let raster = self.raster;
+        self.save(self.path("png"), move |path| {
+            raster.image(&buffer).save_png(path)
+        });
+    }
+
+    /// Start recording, or stop and save current one
+    pub fn toggle_recording(&mut self) {
+        match self.recording {
+            Some(_) => self.stop(),
+            None => {
+                self.recording = Some(Running {
+                    recording: Recording::new(),
+                    started: Instant::now(),
+                    next: Duration::ZERO,
+                })
+            }
+        }
+    }
+
+    fn stop(&mut self) {
+        if let Some(Running { recording, .. }) = self.recording.take() {
+            let raster = self.raster;
+            let path = self.path(&self.format);
+            self.save(path, move |path| recording.save(&raster, path));
+        }
+    }
+
+    /// Add drawn frame to recording, if there is one
+    pub fn frame(&mut self, buffer: &RenderBuffer) {
+        if let Some(running) = &mut self.recording {
+            let at = running.started.elapsed();
+            if at >= running.next {
+                running.recording.push(buffer, at);
+                running.next = at + FRAME_TIME;
+            }
+            let bytes = running.recording.cells() * size_of::<RenderChar>();
+            if at >= MAX_RECORDING || bytes >= MAX_RECORDING_BYTES {
+                self.stop();
+            }
+        }
+    }
+
+    /// How long current recording is running
+    pub fn recording(&self) -> Option<Duration> {
+        self.recording
+            .as_ref()
+            .map(|running| running.started.elapsed())
+    }
+
+    /// Results of saves finished since last call
+    pub fn results(&self) -> Vec<String> {
+        self.results.try_iter().collect()
+    }
+}
@@ -0,0 +1,232 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::style::Colors;
+
+use rusty_ascii_graphics::{
+    split_width, Drawable, Rect, RenderBuffer, Response, Style, TextInput, Widget,
+};
+use rusty_snake::chat::{ChatMessage, MAX_MESSAGE_LEN};
+use rusty_snake::Game;
+
+use crate::hud::Label;
+use crate::theme::fg;
+use crate::THEME;
+
+/// Oldest messages are dropped from local log after this many
+const LOG_SIZE: usize = 200;
+
+/// What chat input wants client to do after key press
+pub enum ChatAction {
+    Nothing,
+    Send(String),
+    Close,
+}
+
+/// Local message log and input line
+pub struct Chat {
+    log: Vec<ChatMessage>,
+    last_id: u64,
+    /// Text being typed, `None` when input is closed
+    pub input: Option<TextInput>,
+    /// Wrapped lines scrolled back from newest one
+    scroll: usize,
+    /// Width log is wrapped to, same as chat box
+    width: usize,
+}
+
+/// Text shown for message, author first unless it's a local notice
+fn message_text(message: &ChatMessage) -> String {
+    if message.author.is_empty() {
+        message.text.clone()
+    } else {
+        format!("{}: {}", message.author, message.text)
+    }
+}
+
+impl Chat {
+    pub fn new() -> Self {
+        Self {
+            log: Vec::new(),
+            last_id: 0,
+            input: None,
+            scroll: 0,
+            width: 1,
+        }
+    }
+
+    /// Wrap log to new chat box width
+    pub fn resize(&mut self, width: usize) {
+        self.width = width.max(1);
+        self.clamp_scroll();
+    }
+
+    // Lines message takes once wrapped, same as drawn by `ChatBox`
+    fn line_count(&self, message: &ChatMessage) -> usize {
+        split_width(&message_text(message), self.width).len()
+    }
+
+    // Keep at least newest line of oldest message on screen
+    fn clamp_scroll(&mut self) {
+        let lines: usize = self.log.iter().map(|m| self.line_count(m)).sum();
+        self.scroll = self.scroll.min(lines.saturating_sub(1));
+    }
+
+    // Add message, keeping scrolled back view where it is
+    fn push(&mut self, message: ChatMessage) {
+        if self.scroll > 0 {
+            self.scroll += self.line_count(&message);
+        }
+        self.log.push(message);
+    }
+
+    pub fn open(&mut self) {
+        let mut input = TextInput::new(0, 0, 0)
+            .max_len(MAX_MESSAGE_LEN)
+            .style(Style::new().fg(THEME.highlight));
+        input.set_focus(true);
+        self.input = Some(input);
+    }
+
+    /// Local message shown in log without author, like results of saving
+    pub fn notice(&mut self, text: String) {
+        self.push(ChatMessage {
+            id: 0,
+            author: String::new(),
+            text,
+        });
+    }
+
+    /// Take messages from snapshot that weren't seen yet
+    pub fn update(&mut self, game: &Game) {
+        // Server restarted, ids start over
+        if game.chat_seq < self.last_id {
+            self.last_id = 0;
+        }
+        let last_id = self.last_id;
+        for message in game.chat.iter().filter(|m| m.id > last_id) {
+            self.push(message.clone());
+        }
+        self.last_id = game.chat_seq;
+        if self.log.len() > LOG_SIZE {
+            self.log.drain(..self.log.len() - LOG_SIZE);
+            self.clamp_scroll();
+        }
+    }
+
+    /// Route key to input line while it's open
+    pub fn handle_key(&mut self, event: &KeyEvent) -> ChatAction {
+        let input = match &mut self.input {
+            Some(input) => input,
+            None => return ChatAction::Nothing,
+        };
+        match event.code {
+            KeyCode::Enter => {
+                let text = input.value().trim().to_string();
+                self.input = None;
+                self.scroll = 0;
+                if text.is_empty() {
+                    return ChatAction::Close;
+                }
+                return ChatAction::Send(text);
+            }
+            KeyCode::Esc => {
+                self.input = None;
+                return ChatAction::Close;
+            }
+            _ if input.handle_key(event) != Response::Ignored => {}
+            KeyCode::PageUp | KeyCode::Up => {
+                self.scroll += 1;
+                self.clamp_scroll();
+            }
+            KeyCode::PageDown | KeyCode::Down => self.scroll = self.scroll.saturating_sub(1),
+            _ => {}
+        }
+        ChatAction::Nothing
+    }
+}
+
+/// Chat log with input line at the bottom, drawn in given rectangle
+pub struct ChatBox<'a> {
+    chat: &'a Chat,
+    game: &'a Game,
+    x: isize,
+    y: isize,
+    width: usize,
+    height: usize,
+}
+
+impl<'a> ChatBox<'a> {
+    pub fn new(
+        chat: &'a Chat,
+        game: &'a Game,
+        (x, y): (isize, isize),
+        (width, height): (usize, usize),
+    ) -> Self {
+        Self {
+            chat,
+            game,
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    // Messages split to lines fitting box width
+    fn lines(&self) -> Vec<(String, Colors)> {
+        let mut lines = Vec::new();
+        for message in &self.chat.log {
+            // Authors who left are drawn in text color
+            let colors = THEME
+                .snake(self.game, &message.author)
+                .map_or(fg(THEME.text), |(body, _)| fg(body));
+            let colors = if message.author.is_empty() {
+                fg(THEME.highlight)
+            } else {
+                colors
+            };
+            for piece in split_width(&message_text(message), self.width.max(1)) {
+                lines.push((piece.to_string(), colors));
+            }
+        }
+        lines
+    }
+}
+
+impl<'a> Drawable for ChatBox<'a> {
+    fn draw(&self, render: &mut RenderBuffer) {
+        if self.height < 2 || self.width < 4 {
+            return;
+        }
+        let title = format!("{:─<width$}", "Chat ", width = self.width);
+        render.draw(&Label::new(
+            self.x,
+            self.y,
+            self.width,
+            title,
+            fg(THEME.title),
+        ));
+
+        let rows = self.height - 2;
+        let lines = self.lines();
+        let end = lines.len().saturating_sub(self.chat.scroll);
+        let start = end.saturating_sub(rows);
+        for (i, (text, colors)) in lines[start..end].iter().enumerate() {
+            let y = self.y + 1 + i as isize;
+            render.draw(&Label::new(self.x, y, self.width, text.clone(), *colors));
+        }
+
+        if let Some(input) = &self.chat.input {
+            let y = self.y + self.height as isize - 1;
+            render.draw(&Label::new(
+                self.x,
+                y,
+                2,
+                "> ".to_string(),
+                fg(THEME.highlight),
+            ));
+            let mut input = input.clone();
+            input.rect = Rect::new(self.x + 2, y, self.width - 2, 1);
+            render.draw(&input);
+        }
+    }
+}
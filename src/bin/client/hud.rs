@@ -0,0 +1,286 @@
+use std::time::Duration;
+
+use crossterm::style::Colors;
+
+use rusty_ascii_graphics::{split_width, str_width, Drawable, RenderBuffer, Span, StyledText};
+use rusty_snake::{Game, Snake};
+
+use crate::theme::fg;
+use crate::{LocalPlayer, THEME};
+
+/// Width of side panel, including gap after the board
+pub const HUD_WIDTH: usize = 26;
+
+/// Single line of text, cut with ellipsis when wider than given width
+pub struct Label {
+    x: isize,
+    y: isize,
+    width: usize,
+    text: String,
+    colors: Colors,
+}
+
+impl Label {
+    pub fn new(x: isize, y: isize, width: usize, text: String, colors: Colors) -> Label {
+        Label {
+            x,
+            y,
+            width,
+            text,
+            colors,
+        }
+    }
+}
+
+impl Drawable for Label {
+    fn draw(&self, render: &mut RenderBuffer) {
+        let span = Span::new(&self.text).colors(self.colors);
+        render.draw(&StyledText::new(self.x, self.y, self.width, 1, vec![span]).ellipsis("…"));
+    }
+}
+
+/// Titled block of lines
+pub struct Panel {
+    x: isize,
+    y: isize,
+    width: usize,
+    title: String,
+    lines: Vec<(String, Colors)>,
+}
+
+impl Panel {
+    pub fn new(x: isize, y: isize, width: usize, title: &str) -> Panel {
+        Panel {
+            x,
+            y,
+            width,
+            title: title.to_string(),
+            lines: Vec::new(),
+        }
+    }
+
+    pub fn line(mut self, text: String, colors: Colors) -> Panel {
+        self.lines.push((text, colors));
+        self
+    }
+
+    /// Rows taken by panel including title
+    pub fn height(&self) -> usize {
+        self.lines.len() + 1
+    }
+}
+
+impl Drawable for Panel {
+    fn draw(&self, render: &mut RenderBuffer) {
+        let title = format!(
+            "{:─<width$}",
+            format!("{} ", self.title),
+            width = self.width
+        );
+        render.draw(&Label::new(
+            self.x,
+            self.y,
+            self.width,
+            title,
+            fg(THEME.title),
+        ));
+        for (i, (text, colors)) in self.lines.iter().enumerate() {
+            let y = self.y + 1 + i as isize;
+            render.draw(&Label::new(self.x, y, self.width, text.clone(), *colors));
+        }
+    }
+}
+
+/// Connection related info not stored in game
+pub struct Status {
+    pub connection: String,
+    pub ping: Option<Duration>,
+    /// How long screen is being recorded
+    pub recording: Option<Duration>,
+}
+
+fn clock(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    format!("{:02}:{:02}", secs / 60, secs % 60)
+}
+
+/// Scoreboard, own stats and status next to the board.
+/// Collapses to one line under the board when terminal is too narrow
+pub struct Hud<'a> {
+    game: &'a Game,
+    players: &'a [LocalPlayer],
+    status: &'a Status,
+    board: (usize, usize),
+    /// Player camera follows instead of own snake
+    spectating: Option<&'a str>,
+}
+
+impl<'a> Hud<'a> {
+    /// `board` is outer size of board including border
+    pub fn new(
+        game: &'a Game,
+        players: &'a [LocalPlayer],
+        status: &'a Status,
+        board: (usize, usize),
+    ) -> Hud<'a> {
+        Hud {
+            game,
+            players,
+            status,
+            board,
+            spectating: None,
+        }
+    }
+
+    pub fn spectating(mut self, name: Option<&'a str>) -> Hud<'a> {
+        self.spectating = name;
+        self
+    }
+
+    // Snakes in scoreboard order
+    fn scores(&self) -> Vec<&'a Snake> {
+        let mut snakes: Vec<_> = self.game.snakes.iter().collect();
+        snakes.sort_by(|a, b| b.score.cmp(&a.score).then(a.name.cmp(&b.name)));
+        snakes
+    }
+
+    fn timer(&self) -> String {
+        clock(self.game.elapsed)
+    }
+
+    fn ping(&self) -> String {
+        match self.status.ping {
+            Some(ping) => format!("{}ms", ping.as_millis()),
+            None => "-".to_string(),
+        }
+    }
+
+    fn panels(&self, x: isize) -> Vec<Panel> {
+        let width = HUD_WIDTH - 2;
+        let mut panels = Vec::new();
+
+        let mut scores = Panel::new(x, 0, width, "Scores");
+        for (place, snake) in self.scores().iter().enumerate() {
+            let colors = if self.spectating == Some(snake.name.as_str()) {
+                fg(THEME.highlight)
+            } else {
+                fg(THEME.player(snake.color).0)
+            };
+            // Cut and pad by display width, wide chars take two columns
+            let name = split_width(&snake.name, width - 9)
+                .first()
+                .copied()
+                .unwrap_or("");
+            let pad = " ".repeat((width - 9).saturating_sub(str_width(name)));
+            let line = format!("{:>2}. {}{} {:>3}", place + 1, name, pad, snake.score);
+            scores = scores.line(line, colors);
+        }
+        panels.push(scores);
+
+        for player in self.players {
+            let mut panel = Panel::new(x, 0, width, &player.name);
+            match self.game.snakes.iter().find(|s| s.name == player.name) {
+                Some(snake) => {
+                    let colors = fg(THEME.player(snake.color).0);
+                    panel = panel
+                        .line(format!("Length {}", snake.body.len()), colors)
+                        .line(format!("Score  {}", snake.score), colors);
+                }
+                None => panel = panel.line("Dead".to_string(), fg(THEME.warning)),
+            }
+            panels.push(panel);
+        }
+
+        let players = format!(
+            "Players {}/{}",
+            self.game.snakes.len(),
+            self.game.max_players
+        );
+        let mut info = Panel::new(x, 0, width, "Match")
+            .line(format!("Time   {}", self.timer()), fg(THEME.text))
+            .line(players, fg(THEME.text))
+            .line(self.status.connection.clone(), fg(THEME.text))
+            .line(format!("Ping   {}", self.ping()), fg(THEME.text));
+        if let Some(recording) = self.status.recording {
+            info = info.line(format!("Rec    {}", clock(recording)), fg(THEME.warning));
+        }
+        panels.push(info);
+        panels
+    }
+
+    fn status_line(&self) -> String {
+        let own = self
+            .players
+            .iter()
+            .filter_map(|p| self.game.snakes.iter().find(|s| s.name == p.name))
+            .map(|s| format!("{} {}", s.name, s.score))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let mut line = format!(
+            "{} | {} | {}/{} | {} | {}",
+            self.timer(),
+            own,
+            self.game.snakes.len(),
+            self.game.max_players,
+            self.status.connection,
+            self.ping()
+        );
+        if let Some(recording) = self.status.recording {
+            line.push_str(&format!(" | Rec {}", clock(recording)));
+        }
+        line
+    }
+
+    // Side panels placed under each other, ones that don't fit left out.
+    // Empty when there is no room for side panel
+    fn layout(&self, (width, height): (usize, usize)) -> Vec<Panel> {
+        let bw = self.board.0;
+        if width < bw + HUD_WIDTH {
+            return Vec::new();
+        }
+        let mut y = 0;
+        let mut panels = Vec::new();
+        for mut panel in self.panels(bw as isize + 2) {
+            if y + panel.height() > height {
+                break;
+            }
+            panel.y = y as isize;
+            y += panel.height() + 1;
+            panels.push(panel);
+        }
+        panels
+    }
+
+    /// Name of player whose scoreboard line is at position,
+    /// in buffer of given size
+    pub fn player_at(&self, size: (usize, usize), x: isize, y: isize) -> Option<&'a str> {
+        // Scores are always the first panel
+        let panels = self.layout(size);
+        let scores = panels.first()?;
+        if x < scores.x || x >= scores.x + scores.width as isize || y <= scores.y {
+            return None;
+        }
+        let place = (y - scores.y - 1) as usize;
+        self.scores().get(place).map(|s| s.name.as_str())
+    }
+}
+
+impl<'a> Drawable for Hud<'a> {
+    fn draw(&self, render: &mut RenderBuffer) {
+        let (bw, bh) = self.board;
+        if render.width() >= bw + HUD_WIDTH {
+            for panel in self.layout((render.width(), render.height())) {
+                render.draw(&panel);
+            }
+        } else if render.height() > bh {
+            let line = self.status_line();
+            render.draw(&Label::new(
+                0,
+                bh as isize,
+                render.width(),
+                line,
+                fg(THEME.text),
+            ));
+        }
+    }
+}
@@ -0,0 +1,315 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use rusty_snake::Direction;
+
+/// Things player can do with keyboard
+#[derive(Debug, Clone, PartialEq)]
+pub enum Action {
+    /// Turn snake of local player with given index
+    Move(usize, Direction),
+    Stop,
+    /// Open chat input
+    Chat,
+    Menu,
+    Quit,
+    Leave,
+    Respawn,
+    ToggleScreen,
+    ToggleUnicode,
+    ToggleFancy,
+    ToggleDebug,
+    ToggleSlowMo,
+    ToggleCamera,
+    ToggleMinimap,
+    /// Save screen as PNG
+    Screenshot,
+    /// Start or stop recording screen as animation
+    Record,
+    Help,
+}
+
+impl Action {
+    /// Parse action name as written in config, like `left` or `up:2`
+    pub fn from_name(name: &str) -> Option<Action> {
+        let (name, player) = match name.split_once(':') {
+            Some((name, player)) => (name, player.parse::<usize>().ok()?.checked_sub(1)?),
+            None => (name, 0),
+        };
+        Some(match name {
+            "up" => Action::Move(player, Direction::Up),
+            "left" => Action::Move(player, Direction::Left),
+            "down" => Action::Move(player, Direction::Down),
+            "right" => Action::Move(player, Direction::Right),
+            "stop" => Action::Stop,
+            "chat" => Action::Chat,
+            "menu" => Action::Menu,
+            "quit" => Action::Quit,
+            "leave" => Action::Leave,
+            "respawn" => Action::Respawn,
+            "toggle_screen" => Action::ToggleScreen,
+            "toggle_unicode" => Action::ToggleUnicode,
+            "toggle_fancy" => Action::ToggleFancy,
+            "toggle_debug" => Action::ToggleDebug,
+            "toggle_slow_mo" => Action::ToggleSlowMo,
+            "toggle_camera" => Action::ToggleCamera,
+            "toggle_minimap" => Action::ToggleMinimap,
+            "screenshot" => Action::Screenshot,
+            "record" => Action::Record,
+            "help" => Action::Help,
+            _ => return None,
+        })
+    }
+
+    /// Human readable description for help screen
+    pub fn describe(&self) -> String {
+        match self {
+            Action::Move(player, dir) => {
+                let dir = format!("{:?}", dir).to_lowercase();
+                if *player == 0 {
+                    format!("Move {}", dir)
+                } else {
+                    format!("Move {} (player {})", dir, player + 1)
+                }
+            }
+            Action::Stop => "Stop".to_string(),
+            Action::Chat => "Chat".to_string(),
+            Action::Menu => "Open menu".to_string(),
+            Action::Quit => "Quit".to_string(),
+            Action::Leave => "Leave game".to_string(),
+            Action::Respawn => "Respawn".to_string(),
+            Action::ToggleScreen => "Toggle game screen".to_string(),
+            Action::ToggleUnicode => "Toggle unicode".to_string(),
+            Action::ToggleFancy => "Toggle fancy graphics".to_string(),
+            Action::ToggleDebug => "Toggle debug view".to_string(),
+            Action::ToggleSlowMo => "Toggle slow motion".to_string(),
+            Action::ToggleCamera => "Toggle follow camera".to_string(),
+            Action::ToggleMinimap => "Toggle minimap".to_string(),
+            Action::Screenshot => "Save screenshot".to_string(),
+            Action::Record => "Start or stop recording".to_string(),
+            Action::Help => "Show this help".to_string(),
+        }
+    }
+}
+
+/// Movement key presets for local players
+#[derive(Clone, Copy)]
+pub enum KeySet {
+    Wasd,
+    Arrows,
+    Ijkl,
+}
+
+impl KeySet {
+    pub fn from_name(name: &str) -> Option<KeySet> {
+        match name {
+            "wasd" => Some(KeySet::Wasd),
+            "arrows" => Some(KeySet::Arrows),
+            "ijkl" => Some(KeySet::Ijkl),
+            _ => None,
+        }
+    }
+
+    /// Keys for up, left, down and right
+    pub fn codes(&self) -> [KeyCode; 4] {
+        match self {
+            KeySet::Wasd => [
+                KeyCode::Char('w'),
+                KeyCode::Char('a'),
+                KeyCode::Char('s'),
+                KeyCode::Char('d'),
+            ],
+            KeySet::Arrows => [KeyCode::Up, KeyCode::Left, KeyCode::Down, KeyCode::Right],
+            KeySet::Ijkl => [
+                KeyCode::Char('i'),
+                KeyCode::Char('j'),
+                KeyCode::Char('k'),
+                KeyCode::Char('l'),
+            ],
+        }
+    }
+}
+
+/// Key together with modifiers it has to be pressed with
+pub type Key = (KeyCode, KeyModifiers);
+
+/// Parse key as written in config, like `w`, `Up`, `F1` or `ctrl+c`
+pub fn parse_key(s: &str) -> Option<Key> {
+    let mut modifiers = KeyModifiers::NONE;
+    let mut rest = s;
+    while let Some((modifier, tail)) = rest.split_once('+') {
+        if tail.is_empty() {
+            // Plus key itself, like `ctrl++`
+            break;
+        }
+        modifiers |= match modifier.to_lowercase().as_str() {
+            "ctrl" => KeyModifiers::CONTROL,
+            "alt" => KeyModifiers::ALT,
+            "shift" => KeyModifiers::SHIFT,
+            _ => return None,
+        };
+        rest = tail;
+    }
+    let mut chars = rest.chars();
+    let code = match (chars.next(), chars.next()) {
+        (Some(c), None) => KeyCode::Char(c),
+        _ => match rest.to_lowercase().as_str() {
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "enter" => KeyCode::Enter,
+            "esc" => KeyCode::Esc,
+            "tab" => KeyCode::Tab,
+            "backspace" => KeyCode::Backspace,
+            "space" => KeyCode::Char(' '),
+            f if f.starts_with('f') => KeyCode::F(f[1..].parse().ok()?),
+            _ => return None,
+        },
+    };
+    Some(normalize((code, modifiers)))
+}
+
+/// Format key the same way `parse_key` accepts it
+pub fn format_key(key: &Key) -> String {
+    let mut s = String::new();
+    if key.1.contains(KeyModifiers::CONTROL) {
+        s.push_str("ctrl+");
+    }
+    if key.1.contains(KeyModifiers::ALT) {
+        s.push_str("alt+");
+    }
+    if key.1.contains(KeyModifiers::SHIFT) {
+        s.push_str("shift+");
+    }
+    match key.0 {
+        KeyCode::Char(' ') => s.push_str("Space"),
+        KeyCode::BackTab => s.push_str("shift+Tab"),
+        KeyCode::Char(c) => s.push(c),
+        KeyCode::F(n) => s.push_str(&format!("F{}", n)),
+        code => s.push_str(&format!("{:?}", code)),
+    }
+    s
+}
+
+// Shift is already part of typed char, so it's not compared for chars.
+// Terminals report shift+Tab as its own `BackTab` key, shift or not
+fn normalize(key: Key) -> Key {
+    match key.0 {
+        KeyCode::Char(_) | KeyCode::BackTab => (key.0, key.1 - KeyModifiers::SHIFT),
+        KeyCode::Tab if key.1.contains(KeyModifiers::SHIFT) => {
+            (KeyCode::BackTab, key.1 - KeyModifiers::SHIFT)
+        }
+        _ => key,
+    }
+}
+
+/// Map from keys to actions
+pub struct KeyBindings {
+    bindings: Vec<(Key, Action)>,
+}
+
+impl KeyBindings {
+    /// Default bindings. Movement comes from key set of each local player,
+    /// first player also gets arrows if nobody else uses them
+    pub fn new(key_sets: &[KeySet]) -> Self {
+        let mut bindings = KeyBindings {
+            bindings: Vec::new(),
+        };
+        for (player, keys) in key_sets.iter().enumerate() {
+            bindings.bind_moves(player, *keys);
+        }
+        if !key_sets.iter().any(|k| matches!(k, KeySet::Arrows)) {
+            bindings.bind_moves(0, KeySet::Arrows);
+        }
+        let none = KeyModifiers::NONE;
+        for (code, modifiers, action) in [
+            (KeyCode::Enter, none, Action::Chat),
+            (KeyCode::Char(' '), none, Action::Stop),
+            (KeyCode::Esc, none, Action::Menu),
+            (KeyCode::Char('c'), KeyModifiers::CONTROL, Action::Quit),
+            (KeyCode::Char('q'), none, Action::Leave),
+            (KeyCode::Char('r'), none, Action::Respawn),
+            (KeyCode::Char('t'), none, Action::ToggleScreen),
+            (KeyCode::Char('u'), none, Action::ToggleUnicode),
+            (KeyCode::Char('f'), none, Action::ToggleFancy),
+            (KeyCode::Char('\\'), none, Action::ToggleDebug),
+            (KeyCode::Char('/'), none, Action::ToggleSlowMo),
+            (KeyCode::Char('v'), none, Action::ToggleCamera),
+            (KeyCode::Char('m'), none, Action::ToggleMinimap),
+            (KeyCode::Char('p'), none, Action::Screenshot),
+            (KeyCode::Char('g'), none, Action::Record),
+            (KeyCode::Char('?'), none, Action::Help),
+            (KeyCode::F(1), none, Action::Help),
+        ] {
+            bindings.bind((code, modifiers), action);
+        }
+        bindings
+    }
+
+    fn bind_moves(&mut self, player: usize, keys: KeySet) {
+        let dirs = [
+            Direction::Up,
+            Direction::Left,
+            Direction::Down,
+            Direction::Right,
+        ];
+        for (code, dir) in keys.codes().into_iter().zip(dirs) {
+            self.bind((code, KeyModifiers::NONE), Action::Move(player, dir));
+        }
+    }
+
+    /// Bind key to action, replacing previous binding of that key
+    pub fn bind(&mut self, key: Key, action: Action) {
+        let key = normalize(key);
+        self.bindings.retain(|(k, _)| *k != key);
+        self.bindings.push((key, action));
+    }
+
+    /// Apply `[keys]` table from config: key name to action name.
+    /// Action `none` removes binding. Returns entries that couldn't be parsed
+    pub fn load(&mut self, table: &toml::value::Table) -> Vec<String> {
+        let mut errors = Vec::new();
+        for (key, action) in table {
+            let key = match parse_key(key) {
+                Some(key) => key,
+                None => {
+                    errors.push(format!("Unknown key: {}", key));
+                    continue;
+                }
+            };
+            match action.as_str() {
+                Some("none") => self.bindings.retain(|(k, _)| *k != key),
+                Some(name) => match Action::from_name(name) {
+                    Some(action) => self.bind(key, action),
+                    None => errors.push(format!("Unknown action: {}", name)),
+                },
+                None => errors.push(format!("Action must be a string: {}", action)),
+            }
+        }
+        errors
+    }
+
+    /// Find action for pressed key
+    pub fn action(&self, event: &KeyEvent) -> Option<&Action> {
+        let key = normalize((event.code, event.modifiers));
+        self.bindings
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, action)| action)
+    }
+
+    /// Lines for help screen, one per action with all its keys
+    pub fn describe(&self) -> Vec<String> {
+        let mut lines: Vec<(Action, Vec<String>)> = Vec::new();
+        for (key, action) in &self.bindings {
+            match lines.iter_mut().find(|(a, _)| a == action) {
+                Some((_, keys)) => keys.push(format_key(key)),
+                None => lines.push((action.clone(), vec![format_key(key)])),
+            }
+        }
+        lines
+            .into_iter()
+            .map(|(action, keys)| format!("{:<14} {}", keys.join(", "), action.describe()))
+            .collect()
+    }
+}
@@ -0,0 +1,946 @@
+use std::fmt::Display;
+// Std stuff
+use std::io::{stdout, BufWriter, Write};
+use std::mem::size_of;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use std::{env, fs, thread};
+
+// Lazy static for runtime static variables
+use lazy_static::lazy_static;
+
+// Tokio
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::broadcast::{channel, Receiver, Sender};
+
+// Crossterm
+use crossterm::cursor::{Hide, Show};
+use crossterm::event::MouseButton;
+use crossterm::style::{Color, Colors, Print, ResetColor, SetColors};
+use crossterm::terminal::{Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{event, execute, terminal, ExecutableCommand, QueueableCommand};
+
+// Rusty Snake logic lib
+use rusty_snake::board::{steer, Board, BoardStyle, DebugOverlay};
+use rusty_snake::camera::Camera;
+use rusty_snake::chat::{command, ChatRelay};
+use rusty_snake::net::{encode_frame, UdpClient, RESEND_INTERVAL};
+use rusty_snake::tick::{LagPolicy, Ticker};
+use rusty_snake::{Direction, Game};
+use rusty_snake::{Signal, SnakeEvent, SnakeEventType};
+
+// Renderings
+use rusty_ascii_graphics::{
+    set_wide_ambiguous, str_width, RectangleShape, RenderChar, ScaleMode, Span, StyledText,
+};
+use rusty_ascii_graphics::{ColorDepth, Compositor, Layer, Rect, RenderBuffer};
+
+// Own modules
+mod anim;
+mod capture;
+mod chat;
+mod hud;
+mod keys;
+mod menu;
+mod minimap;
+mod theme;
+use anim::{Animation, DEFAULT_FRAME_RATE};
+use capture::Capture;
+use chat::{Chat, ChatAction, ChatBox};
+use hud::{Hud, Status, HUD_WIDTH};
+use keys::{Action, KeyBindings, KeySet};
+use menu::{run_main_menu, Choice, Overlay, OverlayAction, Settings};
+use minimap::Minimap;
+use theme::{fg, Theme};
+
+// Global variables
+static APP_RUNNING: AtomicBool = AtomicBool::new(true);
+/// Pause menu is open, offline simulation stops stepping meanwhile
+static PAUSED: AtomicBool = AtomicBool::new(false);
+/// Alternate screen is shown, messages are kept in `NOTICES` instead of printed
+static SCREEN_ACTIVE: AtomicBool = AtomicBool::new(false);
+lazy_static! {
+    static ref NOTICES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+    pub static ref CONF: toml::Value =
+        toml::from_str(&fs::read_to_string("Client.toml").unwrap()).unwrap();
+    pub static ref USERNAME: String = CONF["username"].as_str().unwrap().to_string();
+    pub static ref SERVER_ADDRESS: String = CONF["server_address"].as_str().unwrap().to_string();
+    pub static ref LOCAL_PLAYERS: Vec<LocalPlayer> = local_players();
+    pub static ref KEY_BINDINGS: KeyBindings = key_bindings();
+    pub static ref THEME: Theme = theme();
+    pub static ref FRAME_RATE: u32 = CONF
+        .get("frame_rate")
+        .and_then(|v| v.as_integer())
+        .map_or(DEFAULT_FRAME_RATE, |v| v.max(1) as u32);
+    pub static ref COLOR_DEPTH: ColorDepth = color_depth();
+    pub static ref USE_UDP: bool = CONF.get("transport").and_then(|v| v.as_str()) == Some("udp");
+}
+
+// Local players
+
+#[derive(Clone)]
+pub struct LocalPlayer {
+    name: String,
+    keys: KeySet,
+    /// Color slot asked for at join, server picks a free one otherwise
+    color: Option<u8>,
+}
+
+// Color slot from `color` key of given table
+fn picked_color(table: &toml::Value) -> Option<u8> {
+    table
+        .get("color")
+        .and_then(|v| v.as_integer())
+        .and_then(|v| u8::try_from(v).ok())
+}
+
+// First player comes from `username`, others from `[[local_players]]` tables
+fn local_players() -> Vec<LocalPlayer> {
+    let mut players = vec![LocalPlayer {
+        name: USERNAME.to_string(),
+        keys: KeySet::Wasd,
+        color: picked_color(&CONF),
+    }];
+    if let Some(extra) = CONF.get("local_players").and_then(|v| v.as_array()) {
+        for player in extra {
+            let name = player["username"].as_str().unwrap().to_string();
+            let keys = player
+                .get("keys")
+                .and_then(|v| v.as_str())
+                .and_then(KeySet::from_name)
+                .unwrap_or(KeySet::Arrows);
+            players.push(LocalPlayer {
+                name,
+                keys,
+                color: picked_color(player),
+            });
+        }
+    }
+    players
+}
+
+// Configured theme, default one when it can't be loaded
+fn theme() -> Theme {
+    let name = CONF
+        .get("theme")
+        .and_then(|v| v.as_str())
+        .unwrap_or("default");
+    Theme::load(name).unwrap_or_else(|e| {
+        print_failure(e);
+        Theme::default()
+    })
+}
+
+// Screenshots and recordings go to `capture_dir`
+fn capture() -> Capture {
+    let dir = CONF
+        .get("capture_dir")
+        .and_then(|v| v.as_str())
+        .unwrap_or(".");
+    let format = match CONF.get("recording_format").and_then(|v| v.as_str()) {
+        Some("png") => "png",
+        _ => "gif",
+    };
+    Capture::new(dir.into(), format)
+}
+
+// Configured color depth, guessed from environment when "auto" or unknown
+fn color_depth() -> ColorDepth {
+    match CONF.get("color_depth").and_then(|v| v.as_str()) {
+        Some("truecolor") => ColorDepth::TrueColor,
+        Some("256") => ColorDepth::Ansi256,
+        Some("16") => ColorDepth::Ansi16,
+        _ => ColorDepth::detect(),
+    }
+}
+
+// Defaults adjusted by `[keys]` table from config
+fn key_bindings() -> KeyBindings {
+    let key_sets: Vec<KeySet> = LOCAL_PLAYERS.iter().map(|p| p.keys).collect();
+    let mut bindings = KeyBindings::new(&key_sets);
+    if let Some(table) = CONF.get("keys").and_then(|v| v.as_table()) {
+        for error in bindings.load(table) {
+            print_failure(error);
+        }
+    }
+    bindings
+}
+
+// Rooms listed in main menu, from `[[rooms]]` tables
+fn rooms() -> Vec<(String, String)> {
+    let mut rooms = vec![("Default".to_string(), SERVER_ADDRESS.to_string())];
+    if let Some(extra) = CONF.get("rooms").and_then(|v| v.as_array()) {
+        for room in extra {
+            rooms.push((
+                room["name"].as_str().unwrap().to_string(),
+                room["address"].as_str().unwrap().to_string(),
+            ));
+        }
+    }
+    rooms
+}
+
+fn restore_terminal<T: Write>(stdout: &mut T) {
+    stdout.execute(LeaveAlternateScreen).unwrap();
+    stdout.execute(event::DisableMouseCapture).unwrap();
+    stdout.execute(Show).unwrap();
+    terminal::disable_raw_mode().unwrap();
+    SCREEN_ACTIVE.store(false, Ordering::Relaxed);
+    // Messages that didn't make it to chat before exit
+    for notice in NOTICES.lock().unwrap().drain(..) {
+        print_info(notice);
+    }
+}
+
+// Join game with picked color, if any
+fn join(tx: &Sender<SnakeEvent>, player: &LocalPlayer) {
+    tx.send(SnakeEvent {
+        event_type: SnakeEventType::Signal(Signal::Connect),
+        event_owner: player.name.clone(),
+    })
+    .unwrap();
+    if let Some(color) = player.color {
+        tx.send(SnakeEvent {
+            event_type: SnakeEventType::Color(color),
+            event_owner: player.name.clone(),
+        })
+        .unwrap();
+    }
+}
+
+fn send_for_all(tx: &Sender<SnakeEvent>, players: &[LocalPlayer], event_type: SnakeEventType) {
+    for player in players {
+        tx.send(SnakeEvent {
+            event_type: event_type.clone(),
+            event_owner: player.name.clone(),
+        })
+        .unwrap();
+    }
+}
+
+// Render settings, changed by keys and options menu
+pub struct Options {
+    pub use_fancy: bool,
+    pub use_unicode: bool,
+    pub use_debug: bool,
+    pub use_slow_mo: bool,
+    /// Keep camera on own head even when whole map fits
+    pub camera_follow: bool,
+    /// Show minimap when map doesn't fit the screen
+    pub use_minimap: bool,
+    /// Clicking board turns own snake toward clicked cell
+    pub mouse_steer: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            use_fancy: true,
+            use_unicode: false,
+            use_debug: false,
+            use_slow_mo: false,
+            camera_follow: false,
+            use_minimap: true,
+            mouse_steer: false,
+        }
+    }
+}
+
+// Help functions
+fn border_colors() -> Colors {
+    fg(THEME.border)
+}
+
+// Box listing current key bindings
+fn draw_help(buffer: &mut RenderBuffer, x: isize, y: isize) {
+    let bindings = KEY_BINDINGS.describe();
+    let w = bindings
+        .iter()
+        .map(|l| str_width(l))
+        .max()
+        .unwrap_or(0)
+        .max(8)
+        + 4;
+    let h = bindings.len() + 4;
+    let (w, h) = (w as isize, h as isize);
+    buffer.draw(&RectangleShape::new(x, y, w, h, RenderChar::empty(), true));
+    let border = RenderChar::new('#', border_colors());
+    buffer.draw(&RectangleShape::new(x, y, w, h, border, false));
+    let spans = vec![
+        Span::new("Controls\n\n").fg(THEME.title).bold(),
+        Span::new(&bindings.join("\n")).fg(THEME.text),
+    ];
+    let text = StyledText::new(x + 2, y + 1, w as usize - 4, h as usize - 2, spans);
+    buffer.draw(&text);
+}
+
+// Printing while screen is taken over would mess up diff rendering,
+// so messages go to chat log instead
+fn print_colored<T: Display>(color: Color, data: T) {
+    if SCREEN_ACTIVE.load(Ordering::Relaxed) {
+        NOTICES.lock().unwrap().push(data.to_string());
+        return;
+    }
+    execute! {
+        stdout(),
+        SetColors(Colors {foreground: Some(color), background: None}),
+        Print(data),
+        Print('\n'),
+        ResetColor
+    }
+    .unwrap();
+}
+
+fn print_success<T: Display>(data: T) {
+    print_colored(Color::Green, data);
+}
+
+fn print_warn<T: Display>(data: T) {
+    print_colored(Color::Yellow, data);
+}
+
+fn print_failure<T: Display>(data: T) {
+    print_colored(Color::Red, data);
+}
+
+fn print_info<T: Display>(data: T) {
+    print_colored(Color::Blue, data);
+}
+
+// Function for integrated server thread, used in offline mode
+fn server(tx: Sender<Game>, mut rx: Receiver<SnakeEvent>) {
+    print_success("Entering integrated server thread");
+    let (gw, gh) = (40, 20);
+    let mut game: Game = Game::new(gw, gh, 10, 5, true);
+    game.add_missing_food();
+    let mut ticker = Ticker::new(game.tick_rate, LagPolicy::CatchUp(5));
+    let mut relay = ChatRelay::default();
+    while APP_RUNNING.load(Ordering::Relaxed) {
+        let steps = ticker.wait();
+        while let Ok(mut event) = rx.try_recv() {
+            if let SnakeEventType::Chat(text) = &event.event_type {
+                match relay.check(&event.event_owner, text) {
+                    Ok(text) => event.event_type = SnakeEventType::Chat(text),
+                    Err(_) => continue,
+                }
+            }
+            game.handle_events(event.event_type, event.event_owner);
+        }
+        if !PAUSED.load(Ordering::Relaxed) {
+            for _ in 0..steps {
+                game.step();
+            }
+        }
+        game.add_missing_food();
+        if tx.send(game.clone()).is_err() {
+            break;
+        }
+        ticker.set_rate(game.tick_rate);
+    }
+    print_warn("Exiting integrated server thread");
+}
+
+// Function for communicating with server
+async fn socket(tx: Sender<Game>, mut rx: Receiver<SnakeEvent>, mut socket: TcpStream) {
+    print_success("Entering communication thread");
+    while APP_RUNNING.load(Ordering::Relaxed) {
+        let mut buf_len = [0u8; size_of::<usize>()];
+        tokio::select! {
+            Ok(bytes) = socket.read_exact(&mut buf_len) => {
+                if bytes == 0 {
+                    print_failure("Lost connection to server");
+                    break;
+                }
+                let packet_len = bincode::deserialize(&buf_len).unwrap();
+                let mut buf = vec![0u8; packet_len];
+                socket.read_exact(&mut buf).await.unwrap();
+                let game: Game = bincode::deserialize::<Game>(&buf).unwrap();
+                match tx.send(game) {
+                    Ok(_) => {},
+                    Err(_) => {
+                        print_failure("Lost connection to render thread");
+                        break;
+                    },
+                };
+            },
+            Ok(event) = rx.recv() => {
+                match socket.write_all(&encode_frame(&event)).await{
+                    Ok(_) => {},
+                    Err(_) => {
+                        print_failure("Lost connection to server");
+                        break;
+                    },
+                };
+            },
+            else => {
+                print_failure("Lost connection to render thread & server");
+                break
+            }
+        }
+    }
+    while let Ok(event) = rx.try_recv() {
+        socket.write_all(&encode_frame(&event)).await.unwrap();
+    }
+
+    APP_RUNNING.store(false, Ordering::Relaxed);
+    print_warn("Exiting communication thread");
+}
+
+// Same as socket but over udp transport
+async fn socket_udp(tx: Sender<Game>, mut rx: Receiver<SnakeEvent>, mut socket: UdpClient) {
+    print_success("Entering udp communication thread");
+    let mut resend = tokio::time::interval(RESEND_INTERVAL);
+    while APP_RUNNING.load(Ordering::Relaxed) {
+        tokio::select! {
+            received = socket.recv() => {
+                let game = match received {
+                    Ok(game) => game,
+                    Err(_) => {
+                        print_failure("Lost connection to server");
+                        break;
+                    },
+                };
+                if tx.send(game).is_err() {
+                    print_failure("Lost connection to render thread");
+                    break;
+                }
+            },
+            event = rx.recv() => match event {
+                Ok(event) => {
+                    if socket.send(event).await.is_err() {
+                        print_failure("Lost connection to server");
+                        break;
+                    }
+                },
+                Err(RecvError::Lagged(_)) => {},
+                // Render thread is done, nothing more to send
+                Err(RecvError::Closed) => break,
+            },
+            _ = resend.tick() => {
+                if socket.resend().await.is_err() {
+                    print_failure("Lost connection to server");
+                    break;
+                }
+            },
+        }
+    }
+    // Give last events (like disconnect) a chance to be delivered
+    for _ in 0..5 {
+        if socket.pending() == 0 {
+            break;
+        }
+        let _ = tokio::time::timeout(RESEND_INTERVAL, socket.recv()).await;
+        let _ = socket.resend().await;
+    }
+
+    APP_RUNNING.store(false, Ordering::Relaxed);
+    print_warn("Exiting udp communication thread");
+}
+
+// Main rendering function
+async fn client<T: Write>(
+    mut rx: Receiver<Game>,
+    tx: Sender<SnakeEvent>,
+    players: Vec<LocalPlayer>,
+    mut options: Options,
+    connection: String,
+    mut stdout: T,
+) {
+    print_success("Entered Render Thread");
+    let (width, height) = (
+        terminal::size().unwrap().0 as usize,
+        terminal::size().unwrap().1 as usize,
+    );
+
+    let mut buffer = RenderBuffer::new(width, height);
+    buffer.set_color_depth(*COLOR_DEPTH);
+
+    // Init Stuff
+    print_success("Send Connect");
+    players.iter().for_each(|player| join(&tx, player));
+
+    print_warn("Waiting For Game Copy");
+    let mut game = Game::new(40, 20, 0, 0, true);
+    print_success("Got Game Copy");
+    let mut camera = Camera::new();
+    // Parts of frame drawn separately, composited into buffer
+    let mut layers = Compositor::new();
+
+    let mut game_screen: bool = true;
+    let mut show_help: bool = false;
+    let mut overlay: Option<Overlay> = None;
+    // Player picked on scoreboard, followed by camera instead of own snake
+    let mut spectating: Option<String> = None;
+    let mut chat = Chat::new();
+
+    // Frames are drawn on their own schedule, between snapshots too
+    let mut anim = Animation::new();
+    let frame = Duration::from_secs(1) / *FRAME_RATE;
+    let mut next_frame = Instant::now();
+
+    // Latency is measured by pings echoed back in own snake
+    let started = Instant::now();
+    let mut last_ping: Option<Instant> = None;
+    let mut last_echo = 0;
+    let mut status = Status {
+        connection,
+        ping: None,
+        recording: None,
+    };
+    let mut capture = capture();
+    // Screenshot is taken once next frame is drawn
+    let mut screenshot = false;
+
+    // Game Loop
+    'game_loop: while APP_RUNNING.load(Ordering::Relaxed) {
+        'events_or_game: loop {
+            if let Ok(new_game) = rx.try_recv() {
+                // Drop duplicated and out of order snapshots
+                if !new_game.is_newer_than(&game) {
+                    continue 'events_or_game;
+                }
+                let prev = std::mem::replace(&mut game, new_game);
+                anim.push(prev, &game);
+                chat.update(&game);
+                // Once per snapshot, frames come faster and would flood the event channel
+                for player in players.iter() {
+                    if !game.snakes.iter().any(|s| s.name == player.name) {
+                        join(&tx, player);
+                    }
+                }
+                let echo = game
+                    .snakes
+                    .iter()
+                    .find(|s| s.name == players[0].name)
+                    .map_or(0, |s| s.echo);
+                if echo != 0 && echo != last_echo {
+                    last_echo = echo;
+                    let now = started.elapsed().as_millis() as u64;
+                    status.ping = Some(Duration::from_millis(now.saturating_sub(echo)));
+                }
+                break 'events_or_game;
+            } else {
+                'events: loop {
+                    if event::poll(Duration::from_millis(10)).unwrap() {
+                        match event::read().unwrap() {
+                            event::Event::Key(event) if overlay.is_some() => {
+                                let open = overlay.as_mut().unwrap();
+                                match open.handle(&event::Event::Key(event), &mut options) {
+                                    OverlayAction::Nothing => {}
+                                    OverlayAction::Close => overlay = None,
+                                    OverlayAction::Quit => {
+                                        send_for_all(
+                                            &tx,
+                                            &players,
+                                            SnakeEventType::Signal(Signal::Disconnect),
+                                        );
+                                        break 'game_loop;
+                                    }
+                                }
+                            }
+                            event::Event::Key(event) if chat.input.is_some() => {
+                                if let ChatAction::Send(text) = chat.handle_key(&event) {
+                                    tx.send(SnakeEvent {
+                                        event_type: command(text),
+                                        event_owner: players[0].name.clone(),
+                                    })
+                                    .unwrap();
+                                }
+                            }
+                            event::Event::Key(event) => match KEY_BINDINGS.action(&event) {
+                                Some(Action::Move(player, dir)) => {
+                                    if let (true, Some(player)) =
+                                        (game_screen, players.get(*player))
+                                    {
+                                        tx.send(SnakeEvent {
+                                            event_type: SnakeEventType::Movement(dir.clone()),
+                                            event_owner: player.name.clone(),
+                                        })
+                                        .unwrap();
+                                    }
+                                }
+                                Some(Action::Leave) if game_screen => send_for_all(
+                                    &tx,
+                                    &players,
+                                    SnakeEventType::Signal(Signal::Disconnect),
+                                ),
+                                Some(Action::Respawn) if game_screen => {
+                                    players.iter().for_each(|player| join(&tx, player))
+                                }
+                                Some(Action::Stop) => {
+                                    send_for_all(
+                                        &tx,
+                                        &players,
+                                        SnakeEventType::Movement(Direction::Stop),
+                                    );
+                                }
+                                Some(Action::Chat) if game_screen => chat.open(),
+                                Some(Action::Menu) => overlay = Some(Overlay::pause()),
+                                Some(Action::Quit) => {
+                                    send_for_all(
+                                        &tx,
+                                        &players,
+                                        SnakeEventType::Signal(Signal::Disconnect),
+                                    );
+                                    // APP_RUNNING.store(false, Ordering::Relaxed);
+                                    break 'game_loop;
+                                }
+                                Some(Action::ToggleScreen) => {
+                                    game_screen = !game_screen;
+                                    if game_screen {
+                                        stdout.execute(EnterAlternateScreen).unwrap();
+                                        buffer.invalidate();
+                                    } else {
+                                        stdout.execute(LeaveAlternateScreen).unwrap();
+                                    }
+                                }
+                                Some(Action::ToggleUnicode) => {
+                                    options.use_unicode = !options.use_unicode
+                                }
+                                Some(Action::ToggleFancy) => options.use_fancy = !options.use_fancy,
+                                Some(Action::ToggleDebug) => options.use_debug = !options.use_debug,
+                                Some(Action::ToggleSlowMo) => {
+                                    options.use_slow_mo = !options.use_slow_mo
+                                }
+                                Some(Action::ToggleCamera) => {
+                                    options.camera_follow = !options.camera_follow
+                                }
+                                Some(Action::ToggleMinimap) => {
+                                    options.use_minimap = !options.use_minimap
+                                }
+                                Some(Action::Screenshot) => screenshot = true,
+                                Some(Action::Record) => capture.toggle_recording(),
+                                Some(Action::Help) => show_help = !show_help,
+                                _ => {}
+                            },
+                            event::Event::Resize(_, _) => buffer.resize(
+                                terminal::size().unwrap().0 as usize,
+                                terminal::size().unwrap().1 as usize,
+                            ),
+                            event::Event::Mouse(event) => {
+                                // Routed to whatever layer of last frame is under pointer
+                                let hit = layers.hit(event.column as isize, event.row as isize);
+                                let Some((layer, (x, y))) = hit else {
+                                    continue 'events;
+                                };
+                                let local = event::Event::Mouse(event::MouseEvent {
+                                    column: x as u16,
+                                    row: y as u16,
+                                    ..event
+                                });
+                                if let Some(open) = overlay.as_mut() {
+                                    if layer.name != "overlay" {
+                                        continue 'events;
+                                    }
+                                    match open.handle(&local, &mut options) {
+                                        OverlayAction::Nothing => {}
+                                        OverlayAction::Close => overlay = None,
+                                        OverlayAction::Quit => {
+                                            send_for_all(
+                                                &tx,
+                                                &players,
+                                                SnakeEventType::Signal(Signal::Disconnect),
+                                            );
+                                            break 'game_loop;
+                                        }
+                                    }
+                                    continue 'events;
+                                }
+                                if event.kind != event::MouseEventKind::Down(MouseButton::Left) {
+                                    continue 'events;
+                                }
+                                match layer.name {
+                                    "hud" => {
+                                        let hud = Hud::new(
+                                            &game,
+                                            &players,
+                                            &status,
+                                            (camera.width + 2, camera.height + 2),
+                                        );
+                                        let size = (buffer.width(), buffer.height());
+                                        if let Some(name) = hud.player_at(size, x, y) {
+                                            // Clicking again or on yourself goes back to own snake
+                                            spectating = if spectating.as_deref() == Some(name)
+                                                || name == players[0].name
+                                            {
+                                                None
+                                            } else {
+                                                Some(name.to_string())
+                                            };
+                                        }
+                                    }
+                                    "board" | "debug" if options.mouse_steer => {
+                                        let name = &players[0].name;
+                                        if let Some(event) = steer(&game, &camera, name, (x, y)) {
+                                            tx.send(event).unwrap();
+                                        }
+                                    }
+                                    _ => {}
+                                }
+                            }
+                        };
+                    } else {
+                        break 'events;
+                    }
+                }
+                if Instant::now() >= next_frame {
+                    break 'events_or_game;
+                }
+            }
+        }
+        next_frame = Instant::now() + frame;
+
+        // do logic
+        if last_ping.is_none_or(|t| t.elapsed() >= Duration::from_secs(1)) {
+            last_ping = Some(Instant::now());
+            // Zero means no ping was echoed yet
+            let stamp = started.elapsed().as_millis() as u64 + 1;
+            tx.send(SnakeEvent {
+                event_type: SnakeEventType::Ping(stamp),
+                event_owner: players[0].name.clone(),
+            })
+            .unwrap();
+        }
+
+        PAUSED.store(overlay.is_some(), Ordering::Relaxed);
+
+        //dra
+        if game_screen {
+            buffer.clear(RenderChar::empty());
+
+            // Board takes what is left after side panel, border included.
+            // Without room for the panel HUD collapses to a status line under the board
+            let space = if buffer.width() >= HUD_WIDTH + 22 {
+                (
+                    buffer.width() - HUD_WIDTH - 2,
+                    buffer.height().saturating_sub(2),
+                )
+            } else {
+                (
+                    buffer.width().saturating_sub(2),
+                    buffer.height().saturating_sub(3),
+                )
+            };
+            // Spectated player may have left meanwhile
+            if let Some(name) = &spectating {
+                if !game.snakes.iter().any(|s| s.name == *name) {
+                    spectating = None;
+                }
+            }
+            let followed = spectating.as_deref().unwrap_or(&players[0].name);
+            let target = game
+                .snakes
+                .iter()
+                .find(|s| s.name == followed)
+                .map(|s| s.head);
+            camera.update(
+                game.size,
+                game.teleport,
+                space,
+                target,
+                options.camera_follow || spectating.is_some(),
+            );
+            let (gw, gh) = (camera.width, camera.height);
+            anim.prune();
+            let style = BoardStyle {
+                fancy: options.use_fancy,
+                unicode: options.use_unicode,
+                border: THEME.border,
+                food: THEME.food,
+                players: THEME.players.clone(),
+            };
+            let (width, height) = (buffer.width(), buffer.height());
+            let screen = Rect::new(0, 0, width, height);
+            let board = Rect::new(0, 0, gw + 2, gh + 2);
+            layers.clear();
+            layers
+                // Empty ground takes clicks too, for steering
+                .add(Layer::new("board", board, 0).solid())
+                .draw(&Board::new(&game, &camera, &style, &anim));
+            if options.use_debug {
+                layers
+                    .add(Layer::new("debug", board, 1))
+                    .draw(&DebugOverlay::new(&game, &camera));
+            }
+
+            if options.use_minimap && (gw, gh) != game.size {
+                let mode = if options.use_unicode {
+                    ScaleMode::Braille
+                } else {
+                    ScaleMode::HalfBlock
+                };
+                layers
+                    .add(Layer::new("minimap", board, 2).clip(Rect::new(1, 1, gw, gh)))
+                    .draw(&Minimap::new(&game, (gw, gh), mode));
+            }
+
+            layers.add(Layer::new("hud", screen, 0)).draw(
+                &Hud::new(&game, &players, &status, (gw + 2, gh + 2))
+                    .spectating(spectating.as_deref()),
+            );
+
+            // Chat goes right of HUD when there is room, otherwise under the board
+            let chat_x = gw + 2 + HUD_WIDTH;
+            let chat_rect = if width >= chat_x + 20 {
+                Rect::new(chat_x as isize, 0, width - chat_x, gh + 2)
+            } else {
+                let y = gh + 3;
+                Rect::new(0, y as isize, gw + 2, height.saturating_sub(y))
+            };
+            let chat_size = (chat_rect.width, chat_rect.height);
+            chat.resize(chat_size.0);
+            layers
+                .add(Layer::new("chat", chat_rect, 0))
+                .draw(&ChatBox::new(&chat, &game, (0, 0), chat_size));
+
+            if show_help {
+                draw_help(&mut layers.add(Layer::new("help", screen, 3)).buffer, 2, 1);
+            }
+            if let Some(overlay) = &mut overlay {
+                overlay.draw(&mut layers.add(Layer::new("overlay", screen, 4)).buffer);
+            }
+            buffer.draw(&layers);
+            if screenshot {
+                screenshot = false;
+                capture.screenshot(&buffer);
+            }
+            capture.frame(&buffer);
+            status.recording = capture.recording();
+            for result in capture.results() {
+                chat.notice(result);
+            }
+            for notice in NOTICES.lock().unwrap().drain(..) {
+                chat.notice(notice);
+            }
+
+            buffer.render_to(&mut stdout);
+
+            stdout.flush().unwrap();
+        }
+    }
+    restore_terminal(&mut stdout);
+    print_warn("Exiting Render Thread");
+}
+
+// Established link to the game
+enum Connection {
+    Offline,
+    Tcp(TcpStream),
+    Udp(UdpClient),
+}
+
+async fn connect(address: &str) -> std::io::Result<Connection> {
+    if *USE_UDP {
+        Ok(Connection::Udp(UdpClient::connect(address).await?))
+    } else {
+        Ok(Connection::Tcp(TcpStream::connect(address).await?))
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let mut settings = Settings {
+        name: USERNAME.to_string(),
+        address: SERVER_ADDRESS.to_string(),
+        rooms: rooms(),
+    };
+    let mut options = Options::default();
+    if let Some(steer) = CONF.get("mouse_steer").and_then(|v| v.as_bool()) {
+        options.mouse_steer = steer;
+    }
+    // Config errors are printed before the screen is taken over
+    lazy_static::initialize(&KEY_BINDINGS);
+    lazy_static::initialize(&THEME);
+    if let Some(wide) = CONF.get("wide_ambiguous").and_then(|v| v.as_bool()) {
+        set_wide_ambiguous(wide);
+    }
+
+    let mut stdout = BufWriter::new(stdout());
+    terminal::enable_raw_mode().unwrap();
+    stdout.execute(EnterAlternateScreen).unwrap();
+    stdout.execute(Hide).unwrap();
+    stdout.execute(event::EnableMouseCapture).unwrap();
+    SCREEN_ACTIVE.store(true, Ordering::Relaxed);
+
+    // Menu is skipped when offline mode is requested from command line
+    let mut status = None;
+    let mut skip_menu = env::args().any(|arg| arg == "--offline");
+    if skip_menu && settings.name.is_empty() {
+        skip_menu = false;
+        status = Some("Name can't be empty".to_string());
+    }
+    let connection = loop {
+        let choice = if skip_menu {
+            Choice::Offline
+        } else {
+            run_main_menu(&mut stdout, &mut settings, &mut options, status.take())
+        };
+        match choice {
+            Choice::Offline => break Some(Connection::Offline),
+            Choice::Quit => break None,
+            Choice::Online => match connect(&settings.address).await {
+                Ok(connection) => break Some(connection),
+                Err(e) => status = Some(format!("Can't connect to {}: {}", settings.address, e)),
+            },
+        }
+    };
+    stdout.queue(Clear(ClearType::All)).unwrap();
+    stdout.flush().unwrap();
+
+    let connection = match connection {
+        Some(connection) => connection,
+        None => {
+            restore_terminal(&mut stdout);
+            return;
+        }
+    };
+
+    let mut players = LOCAL_PLAYERS.clone();
+    players[0].name = settings.name.clone();
+    for player in &players {
+        print_info(format!("Your name {}", player.name));
+    }
+
+    let (tx_game, _rx_game) = channel::<Game>(32);
+    let (tx_event, _rx_event) = channel::<SnakeEvent>(32);
+
+    let label = match connection {
+        Connection::Offline => "Offline".to_string(),
+        Connection::Tcp(_) => format!("tcp {}", settings.address),
+        Connection::Udp(_) => format!("udp {}", settings.address),
+    };
+    let socket_handle = match connection {
+        Connection::Offline => {
+            print_info("Playing offline");
+            // Simulation runs in process, events go straight to it
+            thread::Builder::new()
+                .name("Server Thread".to_string())
+                .spawn(move || server(tx_game, _rx_event))
+                .unwrap();
+            None
+        }
+        Connection::Udp(udp) => {
+            print_info(format!("Server address {} (udp)", settings.address));
+            Some(tokio::spawn(socket_udp(tx_game, _rx_event, udp)))
+        }
+        Connection::Tcp(stream) => {
+            print_info(format!("Server address {}", settings.address));
+            Some(tokio::spawn(socket(tx_game, _rx_event, stream)))
+        }
+    };
+    let client_handle = thread::Builder::new()
+        .name("Client Thread".to_string())
+        .spawn(move || client(_rx_game, tx_event, players, options, label, stdout))
+        .unwrap();
+    print_success("Spawned Client thread");
+    client_handle.join().unwrap().await;
+    if let Some(socket_handle) = socket_handle {
+        socket_handle.await.unwrap();
+    } else {
+        APP_RUNNING.store(false, Ordering::Relaxed);
+    }
+    print_warn("Exiting Main Thread");
+}
@@ -0,0 +1,455 @@
+use std::io::Write;
+
+use crossterm::event::{self, Event, KeyCode, MouseEventKind};
+use crossterm::style::Colors;
+use crossterm::terminal;
+
+use rusty_ascii_graphics::{
+    str_width, Button, Checkbox, Focus, Rect, RectangleShape, RenderBuffer, RenderChar, Response,
+    Span, Style, StyledText, TextInput, Widget,
+};
+
+use crate::theme::fg;
+use crate::{border_colors, draw_help, Options, COLOR_DEPTH, THEME};
+
+/// Columns of text inputs in menus, longer values scroll
+const INPUT_WIDTH: usize = 24;
+
+/// Single line of menu
+pub enum Item {
+    Button(String),
+    Toggle(String, bool),
+    Input(String, String),
+}
+
+/// What happened to menu after key press or click
+pub enum MenuEvent {
+    Nothing,
+    /// Button or input was confirmed with Enter or click
+    Activated(usize),
+    /// Toggle flipped or input edited
+    Changed(usize),
+    /// Esc pressed
+    Back,
+}
+
+// Widget of menu line, inputs with their label
+enum Control {
+    Button(Button),
+    Toggle(Checkbox),
+    Input(String, TextInput),
+}
+
+impl Control {
+    fn widget(&mut self) -> &mut dyn Widget {
+        match self {
+            Control::Button(button) => button,
+            Control::Toggle(checkbox) => checkbox,
+            Control::Input(_, input) => input,
+        }
+    }
+
+    // Columns taken after selection cursor
+    fn width(&self) -> usize {
+        match self {
+            Control::Button(button) => button.rect.width,
+            Control::Toggle(checkbox) => checkbox.rect.width,
+            Control::Input(label, _) => str_width(label) + 2 + INPUT_WIDTH,
+        }
+    }
+}
+
+/// Vertical list of items with one selected
+pub struct Menu {
+    pub title: String,
+    controls: Vec<Control>,
+    pub status: Option<String>,
+    focus: Focus,
+}
+
+fn widgets(controls: &mut [Control]) -> Vec<&mut dyn Widget> {
+    controls.iter_mut().map(Control::widget).collect()
+}
+
+impl Menu {
+    pub fn new(title: &str, items: Vec<Item>) -> Self {
+        let focused = Style::new().fg(THEME.highlight);
+        let mut controls: Vec<Control> = items
+            .into_iter()
+            .map(|item| match item {
+                Item::Button(label) => {
+                    Control::Button(Button::new(0, 0, &label).focus_style(focused))
+                }
+                Item::Toggle(label, value) => {
+                    Control::Toggle(Checkbox::new(0, 0, &label, value).focus_style(focused))
+                }
+                Item::Input(label, value) => {
+                    let mut input =
+                        TextInput::new(0, 0, INPUT_WIDTH).cursor_style(focused.reverse());
+                    input.set_value(&value);
+                    Control::Input(label, input)
+                }
+            })
+            .collect();
+        let mut focus = Focus::new();
+        focus.set(0, &mut widgets(&mut controls));
+        Self {
+            title: title.to_string(),
+            controls,
+            status: None,
+            focus,
+        }
+    }
+
+    pub fn toggle(&self, index: usize) -> bool {
+        match &self.controls[index] {
+            Control::Toggle(checkbox) => checkbox.checked(),
+            _ => false,
+        }
+    }
+
+    pub fn input(&self, index: usize) -> &str {
+        match &self.controls[index] {
+            Control::Input(_, input) => input.value(),
+            _ => "",
+        }
+    }
+
+    /// Route key or mouse event to menu. Mouse position is in coordinates
+    /// of buffer menu was last drawn on
+    pub fn handle(&mut self, event: &Event) -> MenuEvent {
+        if let Event::Key(key) = event {
+            if key.code == KeyCode::Esc {
+                return MenuEvent::Back;
+            }
+        }
+        match self.focus.handle(event, &mut widgets(&mut self.controls)) {
+            Some((index, Response::Activated)) => MenuEvent::Activated(index),
+            Some((index, Response::Changed)) => MenuEvent::Changed(index),
+            _ => MenuEvent::Nothing,
+        }
+    }
+
+    // Outer box of menu centered in buffer
+    fn frame(&self, width: usize, height: usize) -> Rect {
+        let status = self.status.as_deref().unwrap_or("");
+        let w = self
+            .controls
+            .iter()
+            .map(|c| c.width() + 2)
+            .chain([str_width(&self.title), str_width(status)])
+            .max()
+            .unwrap_or(0)
+            + 4;
+        let h = self.controls.len() + if status.is_empty() { 4 } else { 6 };
+        let x = (width as isize - w as isize) / 2;
+        let y = (height as isize - h as isize) / 2;
+        Rect::new(x, y, w, h)
+    }
+
+    // Place widgets inside menu box
+    fn layout(&mut self, frame: Rect) {
+        for (i, control) in self.controls.iter_mut().enumerate() {
+            let (x, y) = (frame.x + 4, frame.y + 3 + i as isize);
+            match control {
+                Control::Button(button) => (button.rect.x, button.rect.y) = (x, y),
+                Control::Toggle(checkbox) => (checkbox.rect.x, checkbox.rect.y) = (x, y),
+                Control::Input(label, input) => {
+                    input.rect.x = x + str_width(label) as isize + 2;
+                    input.rect.y = y;
+                }
+            }
+        }
+    }
+
+    /// Draw menu box in the center of buffer
+    pub fn draw(&mut self, buffer: &mut RenderBuffer) {
+        let frame = self.frame(buffer.width(), buffer.height());
+        self.layout(frame);
+        let Rect { x, y, .. } = frame;
+        let (w, h) = (frame.width as isize, frame.height as isize);
+
+        buffer.draw(&RectangleShape::new(x, y, w, h, RenderChar::empty(), true));
+        let border = RenderChar::new('#', border_colors());
+        buffer.draw(&RectangleShape::new(x, y, w, h, border, false));
+        put_str(buffer, x + 2, y + 1, &self.title, fg(THEME.title));
+        for (i, control) in self.controls.iter().enumerate() {
+            let y = y + 3 + i as isize;
+            let colors = if self.focus.focused() == Some(i) {
+                put_str(buffer, x + 2, y, ">", fg(THEME.highlight));
+                fg(THEME.highlight)
+            } else {
+                Colors {
+                    foreground: None,
+                    background: None,
+                }
+            };
+            match control {
+                Control::Button(button) => buffer.draw(button),
+                Control::Toggle(checkbox) => buffer.draw(checkbox),
+                Control::Input(label, input) => {
+                    put_str(buffer, x + 4, y, &format!("{}:", label), colors);
+                    buffer.draw(input);
+                }
+            }
+        }
+        if let Some(status) = &self.status {
+            let y = y + 4 + self.controls.len() as isize;
+            put_str(buffer, x + 2, y, status, fg(THEME.warning));
+        }
+    }
+}
+
+fn put_str(buffer: &mut RenderBuffer, x: isize, y: isize, s: &str, colors: Colors) {
+    let span = Span::new(s).colors(colors);
+    buffer.draw(&StyledText::new(x, y, str_width(s), 1, vec![span]));
+}
+
+/// Screens shown over the game
+pub enum Overlay {
+    Pause(Menu),
+    Options(Menu),
+    Bindings,
+}
+
+/// What in-game overlay wants client to do
+pub enum OverlayAction {
+    Nothing,
+    Close,
+    Quit,
+}
+
+const PAUSE_RESUME: usize = 0;
+const PAUSE_OPTIONS: usize = 1;
+const PAUSE_QUIT: usize = 2;
+
+impl Overlay {
+    pub fn pause() -> Overlay {
+        Overlay::Pause(Menu::new(
+            "Paused",
+            vec![
+                Item::Button("Resume".to_string()),
+                Item::Button("Options".to_string()),
+                Item::Button("Quit".to_string()),
+            ],
+        ))
+    }
+
+    /// Route key or mouse event to overlay. Overlay may replace itself
+    /// with another one
+    pub fn handle(&mut self, event: &Event, options: &mut Options) -> OverlayAction {
+        match self {
+            Overlay::Pause(menu) => match menu.handle(event) {
+                MenuEvent::Back | MenuEvent::Activated(PAUSE_RESUME) => {
+                    return OverlayAction::Close
+                }
+                MenuEvent::Activated(PAUSE_OPTIONS) => {
+                    *self = Overlay::Options(options_menu(options))
+                }
+                MenuEvent::Activated(PAUSE_QUIT) => return OverlayAction::Quit,
+                _ => {}
+            },
+            Overlay::Options(menu) => match handle_options(menu, event, options) {
+                Some(OPTIONS_BINDINGS) => *self = Overlay::Bindings,
+                Some(_) => *self = Overlay::pause(),
+                None => {}
+            },
+            Overlay::Bindings if closes_help(event) => {
+                *self = Overlay::Options(options_menu(options))
+            }
+            Overlay::Bindings => {}
+        }
+        OverlayAction::Nothing
+    }
+
+    pub fn draw(&mut self, buffer: &mut RenderBuffer) {
+        match self {
+            Overlay::Pause(menu) | Overlay::Options(menu) => menu.draw(buffer),
+            Overlay::Bindings => draw_help(buffer, 2, 1),
+        }
+    }
+}
+
+const OPTIONS_BINDINGS: usize = 5;
+
+fn options_menu(options: &Options) -> Menu {
+    Menu::new(
+        "Options",
+        vec![
+            Item::Toggle("Unicode".to_string(), options.use_unicode),
+            Item::Toggle("Fancy graphics".to_string(), options.use_fancy),
+            Item::Toggle("Follow camera".to_string(), options.camera_follow),
+            Item::Toggle("Minimap".to_string(), options.use_minimap),
+            Item::Toggle("Click to steer".to_string(), options.mouse_steer),
+            Item::Button("Key bindings".to_string()),
+            Item::Button("Back".to_string()),
+        ],
+    )
+}
+
+// Help screen is closed by any key or click
+fn closes_help(event: &Event) -> bool {
+    match event {
+        Event::Key(_) => true,
+        Event::Mouse(mouse) => matches!(mouse.kind, MouseEventKind::Down(_)),
+        _ => false,
+    }
+}
+
+// Apply toggles to options. Returns index of activated button or
+// Back item index when menu should be closed
+fn handle_options(menu: &mut Menu, event: &Event, options: &mut Options) -> Option<usize> {
+    match menu.handle(event) {
+        MenuEvent::Changed(_) => {
+            options.use_unicode = menu.toggle(0);
+            options.use_fancy = menu.toggle(1);
+            options.camera_follow = menu.toggle(2);
+            options.use_minimap = menu.toggle(3);
+            options.mouse_steer = menu.toggle(4);
+            None
+        }
+        MenuEvent::Activated(index) => Some(index),
+        MenuEvent::Back => Some(OPTIONS_BINDINGS + 1),
+        MenuEvent::Nothing => None,
+    }
+}
+
+/// Connection settings edited in main menu
+pub struct Settings {
+    pub name: String,
+    pub address: String,
+    /// Named server addresses to pick from
+    pub rooms: Vec<(String, String)>,
+}
+
+/// Result of main menu
+pub enum Choice {
+    Online,
+    Offline,
+    Quit,
+}
+
+enum Screen {
+    Main,
+    Rooms,
+    Options,
+    Bindings,
+}
+
+const MAIN_NAME: usize = 0;
+const MAIN_ADDRESS: usize = 1;
+const MAIN_ONLINE: usize = 2;
+const MAIN_OFFLINE: usize = 3;
+const MAIN_ROOMS: usize = 4;
+const MAIN_OPTIONS: usize = 5;
+const MAIN_QUIT: usize = 6;
+
+fn main_menu(settings: &Settings) -> Menu {
+    Menu::new(
+        "Rusty Snake",
+        vec![
+            Item::Input("Name".to_string(), settings.name.clone()),
+            Item::Input("Server".to_string(), settings.address.clone()),
+            Item::Button("Play online".to_string()),
+            Item::Button("Play offline".to_string()),
+            Item::Button("Rooms".to_string()),
+            Item::Button("Options".to_string()),
+            Item::Button("Quit".to_string()),
+        ],
+    )
+}
+
+fn rooms_menu(settings: &Settings) -> Menu {
+    let mut items: Vec<Item> = settings
+        .rooms
+        .iter()
+        .map(|(name, address)| Item::Button(format!("{} ({})", name, address)))
+        .collect();
+    items.push(Item::Button("Back".to_string()));
+    Menu::new("Rooms", items)
+}
+
+/// Show main menu until player picks how to play.
+/// Terminal has to be in raw mode and alternate screen already
+pub fn run_main_menu<T: Write>(
+    stdout: &mut T,
+    settings: &mut Settings,
+    options: &mut Options,
+    status: Option<String>,
+) -> Choice {
+    let (width, height) = terminal::size().unwrap();
+    let mut buffer = RenderBuffer::new(width as usize, height as usize);
+    buffer.set_color_depth(*COLOR_DEPTH);
+    let mut screen = Screen::Main;
+    let mut menu = main_menu(settings);
+    menu.status = status;
+    loop {
+        buffer.clear(RenderChar::empty());
+        match screen {
+            Screen::Bindings => draw_help(&mut buffer, 2, 1),
+            _ => menu.draw(&mut buffer),
+        }
+        buffer.render_to(stdout);
+        stdout.flush().unwrap();
+
+        let event = event::read().unwrap();
+        if let Event::Resize(w, h) = event {
+            buffer.resize(w as usize, h as usize);
+            continue;
+        }
+        match screen {
+            Screen::Main => match menu.handle(&event) {
+                MenuEvent::Changed(MAIN_NAME) => settings.name = menu.input(MAIN_NAME).to_string(),
+                MenuEvent::Changed(MAIN_ADDRESS) => {
+                    settings.address = menu.input(MAIN_ADDRESS).to_string()
+                }
+                MenuEvent::Activated(
+                    index @ (MAIN_NAME | MAIN_ADDRESS | MAIN_ONLINE | MAIN_OFFLINE),
+                ) => {
+                    if settings.name.is_empty() {
+                        menu.status = Some("Name can't be empty".to_string());
+                    } else if index == MAIN_OFFLINE {
+                        return Choice::Offline;
+                    } else {
+                        return Choice::Online;
+                    }
+                }
+                MenuEvent::Activated(MAIN_ROOMS) => {
+                    screen = Screen::Rooms;
+                    menu = rooms_menu(settings);
+                }
+                MenuEvent::Activated(MAIN_OPTIONS) => {
+                    screen = Screen::Options;
+                    menu = options_menu(options);
+                }
+                MenuEvent::Activated(MAIN_QUIT) | MenuEvent::Back => return Choice::Quit,
+                _ => {}
+            },
+            Screen::Rooms => match menu.handle(&event) {
+                MenuEvent::Activated(index) if index < settings.rooms.len() => {
+                    settings.address = settings.rooms[index].1.clone();
+                    screen = Screen::Main;
+                    menu = main_menu(settings);
+                }
+                MenuEvent::Activated(_) | MenuEvent::Back => {
+                    screen = Screen::Main;
+                    menu = main_menu(settings);
+                }
+                _ => {}
+            },
+            Screen::Options => match handle_options(&mut menu, &event, options) {
+                Some(OPTIONS_BINDINGS) => screen = Screen::Bindings,
+                Some(_) => {
+                    screen = Screen::Main;
+                    menu = main_menu(settings);
+                }
+                None => {}
+            },
+            Screen::Bindings if closes_help(&event) => {
+                screen = Screen::Options;
+                menu = options_menu(options);
+            }
+            Screen::Bindings => {}
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use rusty_ascii_graphics::{
+    Drawable, PixelGrid, RectangleShape, RenderBuffer, RenderChar, Sample, ScaleMode, Scaled,
+};
+use rusty_snake::{Cell, Game};
+
+use crate::{border_colors, THEME};
+
+/// Game cells as pixels. Snakes outweigh walls and walls outweigh food,
+/// so single snake cell stays visible among food when scaled down
+struct CellGrid<'a> {
+    game: &'a Game,
+}
+
+impl<'a> PixelGrid for CellGrid<'a> {
+    fn size(&self) -> (usize, usize) {
+        self.game.size
+    }
+
+    fn sample(&self, x: usize, y: usize) -> Option<Sample> {
+        let (color, weight) = match self
+            .game
+            .get_owners_tables()
+            .get_cell(x as isize, y as isize)
+        {
+            Cell::Player(id) => {
+                let slot = self.game.snakes.iter().find(|s| s.id == id)?.color;
+                (THEME.player(slot).0, 16)
+            }
+            Cell::Wall => (THEME.border, 4),
+            Cell::Food => (THEME.food, 1),
+            Cell::Empty | Cell::Void => return None,
+        };
+        Some(Sample { color, weight })
+    }
+}
+
+/// Downscaled whole map with border, drawn in corner of the board
+pub struct Minimap<'a> {
+    grid: CellGrid<'a>,
+    x: isize,
+    y: isize,
+    width: usize,
+    height: usize,
+    mode: ScaleMode,
+}
+
+impl<'a> Minimap<'a> {
+    /// Fit minimap into bottom right corner of `board` (inner size, placed at 1, 1).
+    /// Size keeps map proportions, assuming chars are twice as tall as wide
+    pub fn new(game: &'a Game, board: (usize, usize), mode: ScaleMode) -> Minimap<'a> {
+        let (mw, mh) = (game.size.0.max(1), game.size.1.max(1));
+        let mut width = (board.0 / 3).clamp(8, 30);
+        let mut height = (width * mh / mw).max(2);
+        if height > board.1 / 2 {
+            height = (board.1 / 2).max(2);
+            width = (height * mw / mh).max(4);
+        }
+        Minimap {
+            grid: CellGrid { game },
+            x: board.0 as isize - width as isize - 1,
+            y: board.1 as isize - height as isize - 1,
+            width,
+            height,
+            mode,
+        }
+    }
+}
+
+impl<'a> Drawable for Minimap<'a> {
+    fn draw(&self, render: &mut RenderBuffer) {
+        let (w, h) = (self.width as isize + 2, self.height as isize + 2);
+        let (x, y) = (self.x, self.y);
+        render.draw(&RectangleShape::new(x, y, w, h, RenderChar::empty(), true));
+        let border = RenderChar::new('#', border_colors());
+        render.draw(&RectangleShape::new(x, y, w, h, border, false));
+        render.draw(&Scaled::new(
+            x + 1,
+            y + 1,
+            self.width,
+            self.height,
+            self.mode,
+            &self.grid,
+        ));
+    }
+}
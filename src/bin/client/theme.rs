@@ -0,0 +1,129 @@
+use std::fs;
+
+use crossterm::style::{Color, Colors};
+
+use rusty_snake::Game;
+
+/// Themes available by name without files
+const BUILTIN: [(&str, &str); 3] = [
+    ("default", include_str!("../../../themes/default.toml")),
+    (
+        "colorblind",
+        include_str!("../../../themes/colorblind.toml"),
+    ),
+    (
+        "monochrome",
+        include_str!("../../../themes/monochrome.toml"),
+    ),
+];
+
+/// Palette used for drawing
+pub struct Theme {
+    pub border: Color,
+    pub food: Color,
+    pub text: Color,
+    pub title: Color,
+    pub highlight: Color,
+    pub warning: Color,
+    /// Body and head color for each player color slot
+    pub players: Vec<(Color, Color)>,
+}
+
+/// Parse color name, `reset`, ansi number or `#rrggbb`
+pub fn parse_color(value: &toml::Value) -> Option<Color> {
+    if let Some(n) = value.as_integer() {
+        return u8::try_from(n).ok().map(Color::AnsiValue);
+    }
+    let s = value.as_str()?;
+    if let Some(hex) = s.strip_prefix('#') {
+        let n = u32::from_str_radix(hex, 16)
+            .ok()
+            .filter(|_| hex.len() == 6)?;
+        return Some(Color::Rgb {
+            r: (n >> 16) as u8,
+            g: (n >> 8) as u8,
+            b: n as u8,
+        });
+    }
+    match s {
+        "reset" => Some(Color::Reset),
+        _ => Color::try_from(s).ok(),
+    }
+}
+
+impl Theme {
+    /// Parse theme file contents
+    pub fn from_toml(s: &str) -> Result<Theme, String> {
+        let table: toml::Value = toml::from_str(s).map_err(|e| e.to_string())?;
+        let color = |key: &str| {
+            let value = table.get(key).ok_or(format!("Missing color: {}", key))?;
+            parse_color(value).ok_or(format!("Bad color for {}: {}", key, value))
+        };
+        let mut players = Vec::new();
+        for pair in table
+            .get("players")
+            .and_then(|v| v.as_array())
+            .ok_or("Missing players list")?
+        {
+            let colors: Vec<Color> = pair
+                .as_array()
+                .map(|pair| pair.iter().filter_map(parse_color).collect())
+                .unwrap_or_default();
+            match colors[..] {
+                [body, head] => players.push((body, head)),
+                _ => return Err(format!("Player colors must be [body, head]: {}", pair)),
+            }
+        }
+        if players.is_empty() {
+            return Err("Players list is empty".to_string());
+        }
+        Ok(Theme {
+            border: color("border")?,
+            food: color("food")?,
+            text: color("text")?,
+            title: color("title")?,
+            highlight: color("highlight")?,
+            warning: color("warning")?,
+            players,
+        })
+    }
+
+    /// Built in theme by name, or theme file when name ends with `.toml`
+    pub fn load(name: &str) -> Result<Theme, String> {
+        if name.ends_with(".toml") {
+            let s = fs::read_to_string(name).map_err(|e| format!("{}: {}", name, e))?;
+            return Theme::from_toml(&s);
+        }
+        match BUILTIN.iter().find(|(n, _)| *n == name) {
+            Some((_, s)) => Theme::from_toml(s),
+            None => Err(format!("Unknown theme: {}", name)),
+        }
+    }
+
+    /// Body and head color of color slot, palette repeats when it's short
+    pub fn player(&self, slot: u8) -> (Color, Color) {
+        self.players[slot as usize % self.players.len()]
+    }
+
+    /// Body and head color of snake with given name
+    pub fn snake(&self, game: &Game, name: &str) -> Option<(Color, Color)> {
+        game.snakes
+            .iter()
+            .find(|s| s.name == name)
+            .map(|s| self.player(s.color))
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::from_toml(BUILTIN[0].1).unwrap()
+    }
+}
+
+/// Foreground only colors
+pub fn fg(color: Color) -> Colors {
+    Colors {
+        foreground: Some(color),
+        background: None,
+    }
+}
@@ -1,3 +1,4 @@
+use std::cmp::Ordering;
 use std::mem::size_of;
 use std::time::Duration;
 use std::{fs, thread};
@@ -12,35 +13,215 @@ use tokio::{
 // use crate::logic::Game;
 // use crate::net::SnakeEvent;
 
-use rusty_snake::{Game, Signal, SnakeEventType};
+use rusty_snake::chat::{censor, ChatRelay, DEFAULT_BURST, DEFAULT_WINDOW, MAX_MESSAGE_LEN};
+use rusty_snake::net::{encode_frame, UdpServer, WsConnection, PEER_TIMEOUT};
+use rusty_snake::tick::{LagPolicy, Ticker, DEFAULT_TICK_RATE};
 use rusty_snake::SnakeEvent;
+use rusty_snake::{Game, Signal, SnakeEventType, DEFAULT_INPUT_BUFFER};
 
 // Own Modules
 // mod logic;
 // mod net;
 
 fn server(tx: Sender<Game>, mut rx: Receiver<SnakeEvent>) {
-    let (gw, gh) = (40, 20);
+    let (gw, gh) = *MAP_SIZE;
     let mut game: Game = Game::new(gw, gh, 10, 5, true);
+    game.input_buffer = *INPUT_BUFFER;
+    game.tick_rate = *TICK_RATE;
     game.add_missing_food();
+    let mut ticker = Ticker::new(game.tick_rate, *LAG_POLICY);
+    let mut relay = chat_relay();
     loop {
-        loop {
-            match rx.try_recv() {
-                Ok(event) => game.handle_events(event.event_type, event.event_owner),
-                Err(_) => break,
+        let steps = ticker.wait();
+        while let Ok(mut event) = rx.try_recv() {
+            match &event.event_type {
+                SnakeEventType::Chat(text) => match relay.check(&event.event_owner, text) {
+                    Ok(text) => event.event_type = SnakeEventType::Chat(text),
+                    Err(e) => {
+                        println!("Chat from {} dropped: {}", event.event_owner, e);
+                        continue;
+                    }
+                },
+                SnakeEventType::Signal(Signal::Disconnect) => relay.forget(&event.event_owner),
+                SnakeEventType::TickRate { rate, secret }
+                    if ADMIN_SECRET.as_ref() != Some(secret) =>
+                {
+                    println!(
+                        "Tick rate {} from {} dropped: wrong secret",
+                        rate, event.event_owner
+                    );
+                    continue;
+                }
+                _ => {}
+            }
+            let players = game.snakes.len();
+            game.handle_events(event.event_type, event.event_owner.clone());
+            match game.snakes.len().cmp(&players) {
+                Ordering::Greater => println!("Adding: {}", event.event_owner),
+                Ordering::Less => println!("Removing: {}", event.event_owner),
+                Ordering::Equal => {}
             }
         }
-        game.step();
+        for _ in 0..steps {
+            game.step();
+        }
         game.add_missing_food();
         tx.send(game.clone()).unwrap();
-        thread::sleep(Duration::from_millis(100));
+        // Rate may be changed at runtime
+        ticker.set_rate(game.tick_rate);
+    }
+}
+
+// Chat limits and blocked words from config
+fn chat_relay() -> ChatRelay {
+    let int = |key: &str, default: usize| {
+        CONF.get(key)
+            .and_then(|v| v.as_integer())
+            .map_or(default, |v| v as usize)
+    };
+    let mut relay = ChatRelay::new(
+        int("chat_max_len", MAX_MESSAGE_LEN),
+        int("chat_burst", DEFAULT_BURST),
+        Duration::from_secs(int("chat_window", DEFAULT_WINDOW.as_secs() as usize) as u64),
+    );
+    let blocklist: Vec<String> = CONF
+        .get("chat_blocklist")
+        .and_then(|v| v.as_array())
+        .map(|words| {
+            words
+                .iter()
+                .filter_map(|w| w.as_str().map(|w| w.to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+    if !blocklist.is_empty() {
+        relay.add_hook(censor(blocklist));
     }
+    relay
 }
 
 lazy_static! {
     pub static ref CONF: toml::Value =
         toml::from_str(&fs::read_to_string("Server.toml").unwrap()).unwrap();
     pub static ref SERVER_ADDRESS: String = CONF["server_address"].as_str().unwrap().to_string();
+    pub static ref UDP_ADDRESS: Option<String> = CONF
+        .get("udp_address")
+        .and_then(|v| v.as_str())
+        .map(|v| v.to_string());
+    pub static ref WS_ADDRESS: Option<String> = CONF
+        .get("ws_address")
+        .and_then(|v| v.as_str())
+        .map(|v| v.to_string());
+    pub static ref MAP_SIZE: (usize, usize) = (
+        CONF.get("map_width")
+            .and_then(|v| v.as_integer())
+            .map_or(40, |v| v as usize),
+        CONF.get("map_height")
+            .and_then(|v| v.as_integer())
+            .map_or(20, |v| v as usize),
+    );
+    pub static ref INPUT_BUFFER: usize = CONF
+        .get("input_buffer")
+        .and_then(|v| v.as_integer())
+        // Turns would all be dropped with no room to queue them
+        .map_or(DEFAULT_INPUT_BUFFER, |v| v.max(1) as usize);
+    pub static ref TICK_RATE: u32 = CONF
+        .get("tick_rate")
+        .and_then(|v| v.as_integer())
+        .map_or(DEFAULT_TICK_RATE, |v| v as u32);
+    // Needed to change tick rate, nobody can when it's not set
+    pub static ref ADMIN_SECRET: Option<String> = CONF
+        .get("admin_secret")
+        .and_then(|v| v.as_str())
+        .filter(|v| !v.is_empty())
+        .map(|v| v.to_string());
+    pub static ref LAG_POLICY: LagPolicy = match CONF.get("lag_policy").and_then(|v| v.as_str()) {
+        Some("skip") => LagPolicy::Skip,
+        _ => LagPolicy::CatchUp(
+            CONF.get("max_catch_up")
+                .and_then(|v| v.as_integer())
+                .map_or(5, |v| v as u32),
+        ),
+    };
+}
+
+// Udp transport shares game and event channels with tcp connections
+async fn udp(mut udp: UdpServer, mut rx_game: Receiver<Game>, tx_event: Sender<SnakeEvent>) {
+    loop {
+        tokio::select! {
+            Ok(event) = udp.recv() => {
+                if tx_event.send(event).is_err() {
+                    println!("Udp event dropped: server thread is gone");
+                }
+            },
+            Ok(g) = rx_game.recv() => {
+                for event in udp.drop_idle(PEER_TIMEOUT) {
+                    println!("User Disconnected (Udp Timeout): {}", event.event_owner);
+                    if tx_event.send(event).is_err() {
+                        println!("Udp event dropped: server thread is gone");
+                    }
+                }
+                if let Err(e) = udp.broadcast(&g).await {
+                    println!("Udp snapshot {} not sent: {}", g.tick, e);
+                }
+            },
+            else => break,
+        }
+    }
+    println!("Exiting udp task");
+}
+
+// Accept websocket connections and serve them like tcp ones
+async fn ws(listener: TcpListener, tx_game: Sender<Game>, tx_event: Sender<SnakeEvent>) {
+    loop {
+        let (socket, _addr) = listener.accept().await.unwrap();
+        println!("Got new websocket connection: {}", _addr.clone());
+        let mut rx_game = tx_game.subscribe();
+        let tx_event = tx_event.clone();
+        tokio::spawn(async move {
+            let mut connection = match WsConnection::accept(socket).await {
+                Ok(connection) => connection,
+                Err(e) => {
+                    println!("Websocket handshake failed {}: {}", _addr.clone(), e);
+                    return;
+                }
+            };
+            loop {
+                tokio::select! {
+                    Ok(g) = rx_game.recv() => {
+                        if connection.send_game(&g).await.is_err() {
+                            println!("User Disconnected (Lost Connection): {}", _addr.clone());
+                            break;
+                        }
+                    },
+                    event = connection.recv_event() => {
+                        let event = match event {
+                            Some(event) => event,
+                            None => {
+                                println!("User Disconnected (Closed): {}", _addr.clone());
+                                break;
+                            }
+                        };
+                        let disconnect = matches!(event.event_type, SnakeEventType::Signal(Signal::Disconnect));
+                        if tx_event.send(event).is_err() {
+                            println!("Websocket event dropped: server thread is gone");
+                        }
+                        if disconnect {
+                            println!("User Disconnected (Event): {}", _addr.clone());
+                            break;
+                        }
+                    }
+                };
+            }
+            // Snakes of closed connection leave like timed out udp ones
+            for event in connection.disconnect_events() {
+                if tx_event.send(event).is_err() {
+                    println!("Websocket event dropped: server thread is gone");
+                }
+            }
+            println!("Exiting {} task", _addr.clone());
+        });
+    }
 }
 
 #[tokio::main]
@@ -60,6 +241,21 @@ async fn main() {
 
     println!("Listening on: {}", listener.local_addr().unwrap());
 
+    if let Some(address) = UDP_ADDRESS.as_ref() {
+        let udp_server = UdpServer::bind(address).await.unwrap();
+        println!("Listening on udp: {}", udp_server.local_addr().unwrap());
+        tokio::spawn(udp(udp_server, tx_game.subscribe(), tx_event.clone()));
+    }
+
+    if let Some(address) = WS_ADDRESS.as_ref() {
+        let ws_listener = TcpListener::bind(address).await.unwrap();
+        println!(
+            "Listening on websocket: {}",
+            ws_listener.local_addr().unwrap()
+        );
+        tokio::spawn(ws(ws_listener, tx_game.clone(), tx_event.clone()));
+    }
+
     loop {
         let (mut socket, _addr) = listener.accept().await.unwrap();
         println!("Got new connection: {}", _addr.clone());
@@ -70,12 +266,7 @@ async fn main() {
                 let mut buf_len = [0u8; size_of::<usize>()];
                 tokio::select! {
                     Ok(g) = rx_game.recv() => {
-                        let serialized_data = bincode::serialize(&g).unwrap();
-                        let serializes_len = bincode::serialize(&serialized_data.len()).unwrap();
-                        let mut buf = Vec::<u8>::new();
-                        buf.extend(&serializes_len);
-                        buf.extend(&serialized_data);
-                        socket.write_all(&buf).await.unwrap();
+                        socket.write_all(&encode_frame(&g)).await.unwrap();
                     },
                     Ok(bytes) = socket.read_exact(&mut buf_len) => {
                         if bytes == 0 {
@@ -0,0 +1,249 @@
+use crossterm::style::{Color, Colors};
+
+use rusty_ascii_graphics::{
+    path_joins, BorderStyle, BoxShape, Drawable, RectangleShape, RenderBuffer, RenderChar,
+};
+
+use crate::camera::Camera;
+use crate::{Cell, Game, Snake, SnakeEvent, SnakeEventType};
+
+/// Turn of named snake toward map cell under board position `pos`,
+/// border included like `Board` draws it. `None` for border cells or no turn
+pub fn steer(game: &Game, camera: &Camera, name: &str, pos: (isize, isize)) -> Option<SnakeEvent> {
+    let (x, y) = (pos.0 - 1, pos.1 - 1);
+    if x < 0 || y < 0 || x >= camera.width as isize || y >= camera.height as isize {
+        return None;
+    }
+    let snake = game.snakes.iter().find(|s| s.name == name)?;
+    let dir = game.direction_toward(snake, camera.to_map((x, y)))?;
+    Some(SnakeEvent {
+        event_type: SnakeEventType::Movement(dir),
+        event_owner: name.to_string(),
+    })
+}
+
+/// Flags and colors board is drawn with
+#[derive(Clone)]
+pub struct BoardStyle {
+    /// Box drawing border and snake glyphs
+    pub fancy: bool,
+    pub unicode: bool,
+    pub border: Color,
+    pub food: Color,
+    /// Body and head color of each player color slot
+    pub players: Vec<(Color, Color)>,
+}
+
+impl Default for BoardStyle {
+    fn default() -> Self {
+        Self {
+            fancy: true,
+            unicode: false,
+            border: Color::Blue,
+            food: Color::Red,
+            players: vec![(Color::Yellow, Color::Green), (Color::Red, Color::Blue)],
+        }
+    }
+}
+
+impl BoardStyle {
+    /// Body and head color of color slot, palette repeats when it's short
+    pub fn player(&self, slot: u8) -> (Color, Color) {
+        self.players[slot as usize % self.players.len()]
+    }
+}
+
+/// Glyph changes between ticks, provided by client animation
+pub trait Effects {
+    /// Head glyph instead of `@`
+    fn head(&self, _game: &Game, _snake: &Snake) -> Option<char> {
+        None
+    }
+    /// Extra cell behind the tail and its glyph
+    fn tail(&self, _game: &Game, _snake: &Snake) -> Option<((isize, isize), char)> {
+        None
+    }
+    /// Food glyph, given normal and small variant
+    fn food(&self, _pos: (isize, isize), glyph: char, _small: char) -> char {
+        glyph
+    }
+    /// Bodies of removed snakes to flash in this frame, with their color slot
+    fn flashes(&self) -> Vec<(&[(isize, isize)], u8)> {
+        Vec::new()
+    }
+}
+
+/// Board without any effects, for static frames
+pub struct NoEffects;
+
+impl Effects for NoEffects {}
+
+fn fg(color: Color) -> Colors {
+    Colors {
+        foreground: Some(color),
+        background: None,
+    }
+}
+
+/// Border, food and snakes of the part of game seen by camera.
+/// Drawn at top left corner of buffer, border included
+pub struct Board<'a> {
+    game: &'a Game,
+    camera: &'a Camera,
+    style: &'a BoardStyle,
+    effects: &'a dyn Effects,
+}
+
+impl<'a> Board<'a> {
+    pub fn new(
+        game: &'a Game,
+        camera: &'a Camera,
+        style: &'a BoardStyle,
+        effects: &'a dyn Effects,
+    ) -> Self {
+        Self {
+            game,
+            camera,
+            style,
+            effects,
+        }
+    }
+
+    // Screen position of map cell, inside border
+    fn view(&self, pos: (isize, isize)) -> Option<(isize, isize)> {
+        self.camera.to_view(pos).map(|(x, y)| (x + 1, y + 1))
+    }
+
+    fn draw_border(&self, buffer: &mut RenderBuffer) {
+        let (w, h) = (self.camera.width as isize, self.camera.height as isize);
+        let colors = fg(self.style.border);
+        if !self.style.fancy {
+            let c = RenderChar::new('#', colors);
+            buffer.draw(&RectangleShape::new(0, 0, w + 2, h + 2, c, false));
+            return;
+        }
+        buffer.draw(&BoxShape::new(
+            0,
+            0,
+            w + 2,
+            h + 2,
+            BorderStyle::Double,
+            colors,
+        ));
+    }
+
+    fn draw_snake(&self, buffer: &mut RenderBuffer, snake: &Snake) {
+        let (tail_color, head_color) = self.style.player(snake.color);
+        let body = snake.get_body();
+        let joins = path_joins(body);
+        for index in (1..body.len()).rev() {
+            if let Some((x, y)) = self.view(body[index]) {
+                let glyph = if self.style.fancy {
+                    BorderStyle::Single.glyph(joins[index])
+                } else {
+                    '0'
+                };
+                buffer.put(x, y, RenderChar::new(glyph, fg(tail_color)));
+            }
+        }
+        // Half cell glyphs show motion in first half of tick
+        if self.style.unicode {
+            if let Some(((x, y), glyph)) = self
+                .effects
+                .tail(self.game, snake)
+                .and_then(|(pos, glyph)| Some((self.view(pos)?, glyph)))
+            {
+                buffer.put(x, y, RenderChar::new(glyph, fg(tail_color)));
+            }
+        }
+        let head = if self.style.unicode {
+            self.effects.head(self.game, snake).unwrap_or('@')
+        } else {
+            '@'
+        };
+        if let Some((x, y)) = self.view(body[0]) {
+            buffer.put(x, y, RenderChar::new(head, fg(head_color)));
+        }
+    }
+}
+
+impl<'a> Drawable for Board<'a> {
+    fn draw(&self, buffer: &mut RenderBuffer) {
+        self.draw_border(buffer);
+
+        let (glyph, small) = if self.style.unicode && self.style.fancy {
+            ('※', '•')
+        } else {
+            ('*', '+')
+        };
+        for food in self.game.get_food() {
+            if let Some((x, y)) = self.view(*food) {
+                let c = self.effects.food(*food, glyph, small);
+                buffer.put(x, y, RenderChar::new(c, fg(self.style.food)));
+            }
+        }
+
+        for snake in &self.game.snakes {
+            self.draw_snake(buffer, snake);
+        }
+
+        let flash = if self.style.unicode { '░' } else { 'x' };
+        for (body, slot) in self.effects.flashes() {
+            for part in body {
+                if let Some((x, y)) = self.view(*part) {
+                    let c = RenderChar::new(flash, fg(self.style.player(slot).1));
+                    buffer.put(x, y, c);
+                }
+            }
+        }
+    }
+}
+
+/// Backgrounds showing what game thinks is in every cell of board, border
+/// included. Cells are transparent, so it tints board drawn below it
+pub struct DebugOverlay<'a> {
+    game: &'a Game,
+    camera: &'a Camera,
+}
+
+impl<'a> DebugOverlay<'a> {
+    pub fn new(game: &'a Game, camera: &'a Camera) -> Self {
+        Self { game, camera }
+    }
+}
+
+impl<'a> Drawable for DebugOverlay<'a> {
+    fn draw(&self, buffer: &mut RenderBuffer) {
+        let colors = [
+            Color::Rgb { r: 127, g: 0, b: 0 },
+            Color::Rgb {
+                r: 127,
+                g: 0,
+                b: 127,
+            },
+            Color::Rgb { r: 0, g: 0, b: 0 },
+            Color::Rgb { r: 0, g: 127, b: 0 },
+        ];
+        let (w, h) = (self.camera.width, self.camera.height);
+        for x in 0..w + 2 {
+            for y in 0..h + 2 {
+                let (x, y) = (x as isize, y as isize);
+                let cell = if x == 0 || y == 0 || x > w as isize || y > h as isize {
+                    Cell::Void
+                } else {
+                    let (mx, my) = self.camera.to_map((x - 1, y - 1));
+                    self.game.get_owners_tables().get_cell(mx, my)
+                };
+                let background = match cell {
+                    Cell::Player(_) => colors[0],
+                    Cell::Food => colors[1],
+                    Cell::Wall | Cell::Void => colors[2],
+                    Cell::Empty => colors[3],
+                };
+                let mut tint = RenderChar::transparent();
+                tint.style.bg = Some(background);
+                buffer.put(x, y, tint);
+            }
+        }
+    }
+}
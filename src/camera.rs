@@ -0,0 +1,128 @@
+/// Part of the map shown on screen.
+/// On teleport maps the view wraps around borders like snakes do
+pub struct Camera {
+    /// Map cell shown in top left corner of view
+    pub x: isize,
+    pub y: isize,
+    /// View size in cells, never bigger than map
+    pub width: usize,
+    pub height: usize,
+    map: (usize, usize),
+    wrap: bool,
+}
+
+/// How much of the view around its center head can move in before scrolling
+const DEAD_ZONE: f32 = 0.5;
+
+impl Default for Camera {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Camera {
+    pub fn new() -> Self {
+        Self {
+            x: 0,
+            y: 0,
+            width: 0,
+            height: 0,
+            map: (0, 0),
+            wrap: false,
+        }
+    }
+
+    /// Fit view into `space` cells and move it so `target` stays inside dead zone.
+    /// Without `follow` whole map is shown when it fits
+    pub fn update(
+        &mut self,
+        map: (usize, usize),
+        wrap: bool,
+        space: (usize, usize),
+        target: Option<(isize, isize)>,
+        follow: bool,
+    ) {
+        if self.map != map {
+            self.x = 0;
+            self.y = 0;
+        }
+        self.map = map;
+        self.wrap = wrap;
+        self.width = map.0.min(space.0);
+        self.height = map.1.min(space.1);
+
+        let fits = self.width == map.0 && self.height == map.1;
+        match target {
+            Some(target) if follow || !fits => {
+                self.x = self.scroll(self.x, target.0, self.width, map.0);
+                self.y = self.scroll(self.y, target.1, self.height, map.1);
+            }
+            _ if fits && !follow => {
+                self.x = 0;
+                self.y = 0;
+            }
+            _ => {}
+        }
+        if !self.wrap {
+            self.x = self.x.clamp(0, (map.0 - self.width) as isize);
+            self.y = self.y.clamp(0, (map.1 - self.height) as isize);
+        }
+    }
+
+    // New view start on one axis so target is inside dead zone
+    fn scroll(&self, start: isize, target: isize, view: usize, map: usize) -> isize {
+        if view == 0 {
+            return start;
+        }
+        let margin = ((view as f32 * (1.0 - DEAD_ZONE)) / 2.0) as isize;
+        let view = view as isize;
+        let mut offset = target - start;
+        if self.wrap {
+            // Shortest way around, so crossing a border doesn't jump the view
+            let map = map as isize;
+            offset = offset.rem_euclid(map);
+            if offset >= view + (map - view) / 2 {
+                offset -= map;
+            }
+        }
+        let start = if offset < margin {
+            target - margin
+        } else if offset >= view - margin {
+            target - view + margin + 1
+        } else {
+            start
+        };
+        if self.wrap {
+            start.rem_euclid(map as isize)
+        } else {
+            start
+        }
+    }
+
+    /// Position in view of map cell, `None` when it's not visible
+    pub fn to_view(&self, pos: (isize, isize)) -> Option<(isize, isize)> {
+        let (mut x, mut y) = (pos.0 - self.x, pos.1 - self.y);
+        if self.wrap {
+            x = x.rem_euclid(self.map.0 as isize);
+            y = y.rem_euclid(self.map.1 as isize);
+        }
+        if x >= 0 && y >= 0 && x < self.width as isize && y < self.height as isize {
+            Some((x, y))
+        } else {
+            None
+        }
+    }
+
+    /// Map cell shown at position in view
+    pub fn to_map(&self, view: (isize, isize)) -> (isize, isize) {
+        let (x, y) = (view.0 + self.x, view.1 + self.y);
+        if self.wrap {
+            (
+                x.rem_euclid(self.map.0 as isize),
+                y.rem_euclid(self.map.1 as isize),
+            )
+        } else {
+            (x, y)
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::SnakeEventType;
+
+/// Longest message in chars, longer ones are cut
+pub const MAX_MESSAGE_LEN: usize = 200;
+/// How many recent messages are kept in game snapshot
+pub const CHAT_HISTORY: usize = 32;
+/// Default rate limit: messages per window per player
+pub const DEFAULT_BURST: usize = 5;
+pub const DEFAULT_WINDOW: Duration = Duration::from_secs(10);
+
+/// Message relayed to every player through game snapshots
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatMessage {
+    /// Increasing number so clients can tell which messages they already have
+    pub id: u64,
+    pub author: String,
+    pub text: String,
+}
+
+/// Why relay refused a message
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChatError {
+    Empty,
+    RateLimited,
+    /// One of the hooks dropped the message
+    Blocked,
+}
+
+impl fmt::Display for ChatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChatError::Empty => write!(f, "empty message"),
+            ChatError::RateLimited => write!(f, "too many messages"),
+            ChatError::Blocked => write!(f, "blocked by filter"),
+        }
+    }
+}
+
+/// Filter run on every message. Gets author and text, returns text to relay
+/// (possibly changed) or `None` to drop the message
+pub type ChatHook = Box<dyn Fn(&str, &str) -> Option<String> + Send>;
+
+/// Server side checks applied before message gets into the game
+pub struct ChatRelay {
+    max_len: usize,
+    burst: usize,
+    window: Duration,
+    sent: HashMap<String, VecDeque<Instant>>,
+    hooks: Vec<ChatHook>,
+}
+
+impl ChatRelay {
+    pub fn new(max_len: usize, burst: usize, window: Duration) -> Self {
+        Self {
+            max_len,
+            burst,
+            window,
+            sent: HashMap::new(),
+            hooks: Vec::new(),
+        }
+    }
+
+    /// Add filter, hooks run in order they were added
+    pub fn add_hook(&mut self, hook: ChatHook) {
+        self.hooks.push(hook);
+    }
+
+    /// Check message and return text that should be relayed
+    pub fn check(&mut self, author: &str, text: &str) -> Result<String, ChatError> {
+        let text: String = text
+            .chars()
+            .filter(|c| !c.is_control())
+            .take(self.max_len)
+            .collect();
+        let mut text = text.trim().to_string();
+        if text.is_empty() {
+            return Err(ChatError::Empty);
+        }
+
+        let now = Instant::now();
+        let sent = self.sent.entry(author.to_string()).or_default();
+        while sent.front().is_some_and(|t| now - *t >= self.window) {
+            sent.pop_front();
+        }
+        if sent.len() >= self.burst {
+            return Err(ChatError::RateLimited);
+        }
+
+        for hook in &self.hooks {
+            text = hook(author, &text).ok_or(ChatError::Blocked)?;
+        }
+        sent.push_back(now);
+        Ok(text)
+    }
+
+    /// Forget rate limit state of player, e.g. after disconnect
+    pub fn forget(&mut self, author: &str) {
+        self.sent.remove(author);
+    }
+}
+
+impl Default for ChatRelay {
+    fn default() -> Self {
+        Self::new(MAX_MESSAGE_LEN, DEFAULT_BURST, DEFAULT_WINDOW)
+    }
+}
+
+/// Profanity hook masking listed words with `*`, case insensitive
+pub fn censor(words: Vec<String>) -> ChatHook {
+    let words: Vec<String> = words.into_iter().map(|w| w.to_lowercase()).collect();
+    Box::new(move |_, text| {
+        let masked: Vec<String> = text
+            .split(' ')
+            .map(|word| {
+                let bare = word.trim_matches(|c: char| !c.is_alphanumeric());
+                if words.contains(&bare.to_lowercase()) {
+                    word.replace(bare, &"*".repeat(bare.chars().count()))
+                } else {
+                    word.to_string()
+                }
+            })
+            .collect();
+        Some(masked.join(" "))
+    })
+}
+
+/// Event for line typed into chat. "/tick_rate <n> <secret>" asks server to
+/// change simulation rate, anything else is sent as message
+pub fn command(text: String) -> SnakeEventType {
+    let mut args = match text.strip_prefix("/tick_rate ") {
+        Some(args) => args.split_whitespace(),
+        None => return SnakeEventType::Chat(text),
+    };
+    match args.next().map(|n| n.parse()) {
+        Some(Ok(rate)) => SnakeEventType::TickRate {
+            rate,
+            secret: args.next().unwrap_or_default().to_string(),
+        },
+        _ => SnakeEventType::Chat(text),
+    }
+}
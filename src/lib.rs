@@ -1,6 +1,17 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 
+pub mod board;
+pub mod camera;
+pub mod chat;
+pub mod net;
+pub mod tick;
+
+use chat::{ChatMessage, CHAT_HISTORY};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Signal {
     Disconnect,
@@ -11,6 +22,18 @@ pub enum Signal {
 pub enum SnakeEventType {
     Movement(Direction),
     Signal(Signal),
+    /// Client timestamp, echoed back in `Snake::echo` to measure latency
+    Ping(u64),
+    /// Chat message, servers pass it through `chat::ChatRelay` first
+    Chat(String),
+    /// Pick color slot, ignored when another snake has it
+    Color(u8),
+    /// Change simulation rate of the whole server, clamped to `1..=tick::MAX_TICK_RATE`.
+    /// Servers drop it unless `secret` matches their admin secret
+    TickRate {
+        rate: u32,
+        secret: String,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,6 +51,25 @@ pub enum Direction {
     Stop,
 }
 
+impl Direction {
+    /// Direction pointing the other way. `Stop` is its own opposite.
+    pub fn opposite(&self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Left => Direction::Right,
+            Direction::Down => Direction::Up,
+            Direction::Right => Direction::Left,
+            Direction::Stop => Direction::Stop,
+        }
+    }
+}
+
+/// Default amount of turns a snake can have queued between two steps
+pub const DEFAULT_INPUT_BUFFER: usize = 3;
+
+/// Number of distinct player color slots, clients map them to theme colors
+pub const PLAYER_COLORS: u8 = 8;
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Snake {
     pub name: String,
@@ -36,25 +78,53 @@ pub struct Snake {
     pub head: (isize, isize),
     pub direction: Direction,
     pub moved_from: Direction,
+    pub queued: VecDeque<Direction>,
     pub alive: bool,
+    pub score: usize,
+    pub echo: u64,
+    /// Color slot, below `PLAYER_COLORS`
+    pub color: u8,
 }
 
 impl Snake {
     pub fn new(x: isize, y: isize, name: String, id: usize) -> Self {
         Self {
-            name: name,
-            id: id,
+            name,
+            id,
             body: Vec::from([(x, y)]),
             head: (x, y),
             direction: Direction::Stop,
             moved_from: Direction::Stop,
+            queued: VecDeque::new(),
             alive: true,
+            score: 0,
+            echo: 0,
+            color: 0,
         }
     }
 
     pub fn get_body(&self) -> &Vec<(isize, isize)> {
         &self.body
     }
+
+    /// Queue turn to be applied on one of the next steps.
+    /// Reversals and repeats of the last queued direction are dropped,
+    /// as well as anything that doesn't fit in `depth`.
+    pub fn queue_direction(&mut self, dir: Direction, depth: usize) {
+        if let Direction::Stop = dir {
+            self.queued.clear();
+            self.direction = dir;
+            return;
+        }
+        let (last, forbidden) = match self.queued.back() {
+            Some(last) => (last.clone(), last.opposite()),
+            None => (self.direction.clone(), self.moved_from.clone()),
+        };
+        if dir == last || dir == forbidden || self.queued.len() >= depth {
+            return;
+        }
+        self.queued.push_back(dir);
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -112,10 +182,16 @@ pub struct Game {
     pub food_amount: usize,
     pub max_players: usize,
     pub teleport: bool,
+    pub input_buffer: usize,
+    pub tick_rate: u32,
     // Data
+    pub tick: u64,
+    pub elapsed: Duration,
     pub snakes: Vec<Snake>,
     pub food: Vec<(isize, isize)>,
     pub cells: CellManager,
+    pub chat: VecDeque<ChatMessage>,
+    pub chat_seq: u64,
 }
 
 impl Game {
@@ -128,12 +204,31 @@ impl Game {
     ) -> Self {
         Self {
             size: (width, height),
-            food_amount: food_amount,
+            food_amount,
             max_players: players,
-            teleport: teleport,
+            teleport,
+            input_buffer: DEFAULT_INPUT_BUFFER,
+            tick_rate: tick::DEFAULT_TICK_RATE,
+            tick: 0,
+            elapsed: Duration::ZERO,
             snakes: Vec::new(),
             food: Vec::new(),
             cells: CellManager::new(width, height),
+            chat: VecDeque::new(),
+            chat_seq: 0,
+        }
+    }
+
+    /// Fill cell grid from food and living snakes, for snapshots sent without it
+    pub fn rebuild_cells(&mut self) {
+        self.cells = CellManager::new(self.size.0, self.size.1);
+        for food in &self.food {
+            self.cells.set_cell(food.0, food.1, Cell::Food);
+        }
+        for snake in self.snakes.iter().filter(|s| s.alive) {
+            for part in &snake.body {
+                self.cells.set_cell(part.0, part.1, Cell::Player(snake.id));
+            }
         }
     }
 
@@ -163,17 +258,21 @@ impl Game {
         if self.snakes.len() >= self.max_players {
             return false;
         }
-        if let Some(_) = self.snakes.iter().position(|s| *s.name == name) {
+        if self.snakes.iter().position(|s| *s.name == name).is_some() {
             return false;
         }
-        println!("Adding: {name}");
         let mut rng = rand::thread_rng();
         let pos = (
             rng.gen_range(0..self.size.0 as isize),
             rng.gen_range(0..self.size.1 as isize),
         );
         let id = self.snakes.len();
-        self.snakes.push(Snake::new(pos.0, pos.1, name, id));
+        let mut snake = Snake::new(pos.0, pos.1, name, id);
+        // First slot nobody uses, shared only when all are taken
+        snake.color = (0..PLAYER_COLORS)
+            .find(|c| !self.snakes.iter().any(|s| s.color == *c))
+            .unwrap_or(id as u8 % PLAYER_COLORS);
+        self.snakes.push(snake);
         self.cells.set_cell(pos.0, pos.1, Cell::Player(id));
         true
     }
@@ -183,7 +282,6 @@ impl Game {
             Some(index) => index,
             None => return,
         };
-        println!("Removing: {name}");
         let current_snake = self.snakes.remove(snake_index);
         for part in current_snake.body {
             self.cells.set_cell(part.0, part.1, Cell::Empty);
@@ -191,7 +289,14 @@ impl Game {
     }
 
     pub fn step(&mut self) {
+        self.tick += 1;
+        self.elapsed += self.tick_period();
         for snake in &mut self.snakes {
+            // Apply next queued turn
+            if let Some(dir) = snake.queued.pop_front() {
+                snake.direction = dir;
+            }
+
             // Process Movement
             let mut next_pos = snake.head;
             match snake.direction {
@@ -252,6 +357,7 @@ impl Game {
                             self.cells
                                 .set_cell(next_pos.0, next_pos.1, Cell::Player(snake.id));
                             self.food.retain(|f| *f != snake.head);
+                            snake.score += 1;
                         }
                         match snake.direction {
                             Direction::Up => {
@@ -272,7 +378,7 @@ impl Game {
                 }
             }
         }
-        self.snakes.retain(|s| s.alive == true);
+        self.snakes.retain(|s| s.alive);
     }
     pub fn handle_events(&mut self, event: SnakeEventType, name: String) {
         // Find current snake by name
@@ -280,34 +386,34 @@ impl Game {
         // process events
         match event {
             SnakeEventType::Movement(dir) => {
+                let depth = self.input_buffer;
                 let current_snake = match self.get_snake_mut(name.clone()) {
                     Some(snake) => snake,
                     None => return,
                 };
-                match dir {
-                    Direction::Up => {
-                        if Direction::Up != current_snake.moved_from {
-                            current_snake.direction = dir
-                        }
-                    }
-                    Direction::Left => {
-                        if Direction::Left != current_snake.moved_from {
-                            current_snake.direction = dir
-                        }
-                    }
-                    Direction::Down => {
-                        if Direction::Down != current_snake.moved_from {
-                            current_snake.direction = dir
-                        }
-                    }
-                    Direction::Right => {
-                        if Direction::Right != current_snake.moved_from {
-                            current_snake.direction = dir
-                        }
-                    }
-                    Direction::Stop => current_snake.direction = dir,
+                current_snake.queue_direction(dir, depth);
+            }
+            SnakeEventType::Ping(stamp) => {
+                if let Some(snake) = self.get_snake_mut(name) {
+                    snake.echo = stamp;
                 }
             }
+            SnakeEventType::Chat(text) => self.push_chat(name, text),
+            SnakeEventType::Color(color) => {
+                let taken = self
+                    .snakes
+                    .iter()
+                    .any(|s| s.color == color && s.name != name);
+                if taken || color >= PLAYER_COLORS {
+                    return;
+                }
+                if let Some(snake) = self.get_snake_mut(name) {
+                    snake.color = color;
+                }
+            }
+            SnakeEventType::TickRate { rate, .. } => {
+                self.tick_rate = rate.clamp(1, tick::MAX_TICK_RATE);
+            }
             SnakeEventType::Signal(signal) => match signal {
                 Signal::Disconnect => {
                     self.remove_player(name);
@@ -319,6 +425,19 @@ impl Game {
         }
     }
 
+    /// Add message to chat history, dropping oldest ones over the limit
+    pub fn push_chat(&mut self, author: String, text: String) {
+        self.chat_seq += 1;
+        self.chat.push_back(ChatMessage {
+            id: self.chat_seq,
+            author,
+            text,
+        });
+        while self.chat.len() > CHAT_HISTORY {
+            self.chat.pop_front();
+        }
+    }
+
     fn get_snake_mut(&mut self, name: String) -> Option<&mut Snake> {
         let mut current_snake: Option<&mut Snake> = None;
         for snake in &mut self.snakes {
@@ -339,6 +458,52 @@ impl Game {
         current_snake
     }
 
+    /// Duration of one step at current tick rate
+    pub fn tick_period(&self) -> Duration {
+        Duration::from_secs(1) / self.tick_rate.max(1)
+    }
+
+    /// Check if this snapshot comes after `other` and should replace it
+    pub fn is_newer_than(&self, other: &Game) -> bool {
+        self.tick > other.tick
+    }
+
+    /// Turn bringing snake closer to target cell, along axis where it's
+    /// further away. Never back into itself, `None` when already there.
+    /// Ways across edges count when map wraps around
+    pub fn direction_toward(&self, snake: &Snake, target: (isize, isize)) -> Option<Direction> {
+        let delta = |from: isize, to: isize, size: usize| {
+            let size = size as isize;
+            let d = to - from;
+            if self.teleport && d.abs() * 2 > size {
+                d - d.signum() * size
+            } else {
+                d
+            }
+        };
+        let dx = delta(snake.head.0, target.0, self.size.0);
+        let dy = delta(snake.head.1, target.1, self.size.1);
+        let horizontal = match dx.signum() {
+            1 => Some(Direction::Right),
+            -1 => Some(Direction::Left),
+            _ => None,
+        };
+        let vertical = match dy.signum() {
+            1 => Some(Direction::Down),
+            -1 => Some(Direction::Up),
+            _ => None,
+        };
+        let (first, second) = if dx.abs() >= dy.abs() {
+            (horizontal, vertical)
+        } else {
+            (vertical, horizontal)
+        };
+        [first, second]
+            .into_iter()
+            .flatten()
+            .find(|dir| *dir != snake.moved_from)
+    }
+
     pub fn get_food(&self) -> &Vec<(isize, isize)> {
         &self.food
     }
@@ -0,0 +1,352 @@
+use std::collections::{HashMap, VecDeque};
+use std::io;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+
+use futures_util::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use tokio::net::{TcpStream, ToSocketAddrs, UdpSocket};
+use tokio_tungstenite::tungstenite::handshake::server::{Request, Response};
+use tokio_tungstenite::tungstenite::{Error as WsError, Message};
+use tokio_tungstenite::WebSocketStream;
+
+use crate::{CellManager, Game, Signal, SnakeEvent, SnakeEventType};
+
+/// Biggest datagram we are ready to receive
+const MAX_DATAGRAM: usize = 65536;
+
+/// Biggest payload that fits in one udp datagram
+pub const MAX_PAYLOAD: usize = 65507;
+
+/// How often client repeats events that weren't acknowledged yet
+pub const RESEND_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How long server keeps udp peer without hearing from it
+pub const PEER_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Serialize message and prefix it with its length, as sent over tcp
+pub fn encode_frame<T: Serialize>(data: &T) -> Vec<u8> {
+    let serialized_data = bincode::serialize(data).unwrap();
+    let serializes_len = bincode::serialize(&serialized_data.len()).unwrap();
+    let mut buf = Vec::<u8>::with_capacity(serializes_len.len() + serialized_data.len());
+    buf.extend(&serializes_len);
+    buf.extend(&serialized_data);
+    buf
+}
+
+/// Single udp datagram
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Packet {
+    /// Reliable event from client. Sequence numbers start from 1
+    Event { seq: u32, event: SnakeEvent },
+    /// All events up to given sequence number were received
+    Ack(u32),
+    /// Unreliable game state, older ones are dropped by receiver.
+    /// Sent without cell grid, receiver rebuilds it from snakes and food
+    Snapshot { seq: u32, game: Game },
+}
+
+async fn send_packet(socket: &UdpSocket, packet: &Packet, addr: SocketAddr) -> io::Result<()> {
+    let buf = bincode::serialize(packet).unwrap();
+    socket.send_to(&buf, addr).await?;
+    Ok(())
+}
+
+fn is_peer_gone(e: &io::Error) -> bool {
+    matches!(
+        e.kind(),
+        io::ErrorKind::ConnectionRefused | io::ErrorKind::ConnectionReset
+    )
+}
+
+/// Client side of udp transport
+pub struct UdpClient {
+    socket: UdpSocket,
+    server: SocketAddr,
+    next_seq: u32,
+    pending: VecDeque<(u32, SnakeEvent)>,
+    last_snapshot: u32,
+}
+
+impl UdpClient {
+    pub async fn connect<A: ToSocketAddrs>(addr: A) -> io::Result<Self> {
+        let server = tokio::net::lookup_host(addr)
+            .await?
+            .next()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "no address"))?;
+        let bind: SocketAddr = if server.is_ipv4() {
+            "0.0.0.0:0".parse().unwrap()
+        } else {
+            "[::]:0".parse().unwrap()
+        };
+        let socket = UdpSocket::bind(bind).await?;
+        socket.connect(server).await?;
+        Ok(Self {
+            socket,
+            server,
+            next_seq: 1,
+            pending: VecDeque::new(),
+            last_snapshot: 0,
+        })
+    }
+
+    /// Queue event for reliable delivery and send it right away
+    pub async fn send(&mut self, event: SnakeEvent) -> io::Result<()> {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        self.pending.push_back((seq, event.clone()));
+        send_packet(&self.socket, &Packet::Event { seq, event }, self.server).await
+    }
+
+    /// Repeat unacknowledged events. Also keeps connection alive when idle
+    pub async fn resend(&mut self) -> io::Result<()> {
+        if self.pending.is_empty() {
+            return send_packet(&self.socket, &Packet::Ack(self.last_snapshot), self.server).await;
+        }
+        for (seq, event) in &self.pending {
+            let packet = Packet::Event {
+                seq: *seq,
+                event: event.clone(),
+            };
+            send_packet(&self.socket, &packet, self.server).await?;
+        }
+        Ok(())
+    }
+
+    /// Number of events waiting for acknowledgement
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Wait for next fresh snapshot. Handles acknowledgements on the way
+    pub async fn recv(&mut self) -> io::Result<Game> {
+        let mut buf = vec![0u8; MAX_DATAGRAM];
+        loop {
+            let len = self.socket.recv(&mut buf).await?;
+            match bincode::deserialize::<Packet>(&buf[..len]) {
+                Ok(Packet::Ack(seq)) => self.pending.retain(|(s, _)| *s > seq),
+                Ok(Packet::Snapshot { seq, mut game }) => {
+                    if seq > self.last_snapshot {
+                        self.last_snapshot = seq;
+                        game.rebuild_cells();
+                        return Ok(game);
+                    }
+                }
+                Ok(Packet::Event { .. }) | Err(_) => {}
+            }
+        }
+    }
+}
+
+struct Peer {
+    names: Vec<String>,
+    acked: u32,
+    last_seen: Instant,
+}
+
+/// Server side of udp transport
+pub struct UdpServer {
+    socket: UdpSocket,
+    peers: HashMap<SocketAddr, Peer>,
+    seq: u32,
+}
+
+impl UdpServer {
+    pub async fn bind<A: ToSocketAddrs>(addr: A) -> io::Result<Self> {
+        Ok(Self {
+            socket: UdpSocket::bind(addr).await?,
+            peers: HashMap::new(),
+            seq: 0,
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.socket.local_addr()
+    }
+
+    /// Amount of known peers
+    pub fn peers(&self) -> usize {
+        self.peers.len()
+    }
+
+    /// Wait for next in-order event from any peer.
+    /// Duplicates and events arriving ahead of missing ones are dropped,
+    /// client repeats them until acknowledged.
+    pub async fn recv(&mut self) -> io::Result<SnakeEvent> {
+        let mut buf = vec![0u8; MAX_DATAGRAM];
+        loop {
+            let (len, addr) = match self.socket.recv_from(&mut buf).await {
+                Ok(received) => received,
+                // Some peer went away, it will be dropped by timeout
+                Err(e) if is_peer_gone(&e) => continue,
+                Err(e) => return Err(e),
+            };
+            let packet = match bincode::deserialize::<Packet>(&buf[..len]) {
+                Ok(packet) => packet,
+                Err(_) => continue,
+            };
+            let peer = self.peers.entry(addr).or_insert(Peer {
+                names: Vec::new(),
+                acked: 0,
+                last_seen: Instant::now(),
+            });
+            peer.last_seen = Instant::now();
+            if let Packet::Event { seq, event } = packet {
+                let fresh = seq == peer.acked + 1;
+                if fresh {
+                    peer.acked = seq;
+                    if !peer.names.contains(&event.event_owner) {
+                        peer.names.push(event.event_owner.clone());
+                    }
+                }
+                let ack = peer.acked;
+                if let SnakeEventType::Signal(Signal::Disconnect) = event.event_type {
+                    if fresh {
+                        peer.names.retain(|name| *name != event.event_owner);
+                    }
+                }
+                match send_packet(&self.socket, &Packet::Ack(ack), addr).await {
+                    Err(e) if !is_peer_gone(&e) => return Err(e),
+                    _ => {}
+                }
+                if fresh {
+                    return Ok(event);
+                }
+            }
+        }
+    }
+
+    /// Send snapshot to every peer. Peers that can't be reached are skipped.
+    /// Fails with `InvalidData` when snapshot doesn't fit in one datagram
+    pub async fn broadcast(&mut self, game: &Game) -> io::Result<()> {
+        self.seq += 1;
+        let mut game = game.clone();
+        game.cells = CellManager::new(0, 0);
+        let buf = bincode::serialize(&Packet::Snapshot {
+            seq: self.seq,
+            game,
+        })
+        .unwrap();
+        if buf.len() > MAX_PAYLOAD {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("snapshot of {} bytes doesn't fit in datagram", buf.len()),
+            ));
+        }
+        for addr in self.peers.keys() {
+            if let Err(e) = self.socket.send_to(&buf, addr).await {
+                if !is_peer_gone(&e) {
+                    return Err(e);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Forget peers silent for longer than `timeout`.
+    /// Returns disconnect events for snakes they controlled
+    pub fn drop_idle(&mut self, timeout: Duration) -> Vec<SnakeEvent> {
+        let mut events = Vec::new();
+        self.peers.retain(|_, peer| {
+            if peer.last_seen.elapsed() <= timeout {
+                return true;
+            }
+            for name in &peer.names {
+                events.push(SnakeEvent {
+                    event_type: SnakeEventType::Signal(Signal::Disconnect),
+                    event_owner: name.clone(),
+                });
+            }
+            false
+        });
+        events
+    }
+}
+
+/// Encoding used for messages on websocket connection
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WsFormat {
+    /// Text frames with json, default
+    Json,
+    /// Binary frames with bincode, same payload as tcp frames without length
+    Binary,
+}
+
+/// Server side of websocket connection.
+/// Format is chosen by request path: `/binary` for bincode, anything else for json
+pub struct WsConnection {
+    stream: WebSocketStream<TcpStream>,
+    format: WsFormat,
+    /// Snakes joined through this connection
+    names: Vec<String>,
+}
+
+impl WsConnection {
+    // Handshake callback signature is dictated by tungstenite
+    #[allow(clippy::result_large_err)]
+    pub async fn accept(stream: TcpStream) -> Result<Self, WsError> {
+        let mut format = WsFormat::Json;
+        let stream = tokio_tungstenite::accept_hdr_async(stream, |req: &Request, res: Response| {
+            if req.uri().path().trim_end_matches('/') == "/binary" {
+                format = WsFormat::Binary;
+            }
+            Ok(res)
+        })
+        .await?;
+        Ok(Self {
+            stream,
+            format,
+            names: Vec::new(),
+        })
+    }
+
+    pub fn format(&self) -> WsFormat {
+        self.format
+    }
+
+    pub async fn send_game(&mut self, game: &Game) -> Result<(), WsError> {
+        let message = match self.format {
+            WsFormat::Json => Message::Text(serde_json::to_string(game).unwrap()),
+            WsFormat::Binary => Message::Binary(bincode::serialize(game).unwrap()),
+        };
+        self.stream.send(message).await
+    }
+
+    /// Wait for next event. Malformed messages are skipped.
+    /// Returns None when connection is closed
+    pub async fn recv_event(&mut self) -> Option<SnakeEvent> {
+        while let Some(message) = self.stream.next().await {
+            let event = match message.ok()? {
+                Message::Text(text) => serde_json::from_str(&text).ok(),
+                Message::Binary(data) => bincode::deserialize(&data).ok(),
+                Message::Close(_) => return None,
+                _ => None,
+            };
+            if let Some(event) = event {
+                self.track(&event);
+                return Some(event);
+            }
+        }
+        None
+    }
+
+    fn track(&mut self, event: &SnakeEvent) {
+        if let SnakeEventType::Signal(Signal::Disconnect) = event.event_type {
+            self.names.retain(|name| *name != event.event_owner);
+        } else if !self.names.contains(&event.event_owner) {
+            self.names.push(event.event_owner.clone());
+        }
+    }
+
+    /// Disconnect events for snakes still joined through this connection,
+    /// to be sent when it closes
+    pub fn disconnect_events(&mut self) -> Vec<SnakeEvent> {
+        self.names
+            .drain(..)
+            .map(|name| SnakeEvent {
+                event_type: SnakeEventType::Signal(Signal::Disconnect),
+                event_owner: name,
+            })
+            .collect()
+    }
+}
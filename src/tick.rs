@@ -0,0 +1,106 @@
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Default simulation rate in ticks per second
+pub const DEFAULT_TICK_RATE: u32 = 10;
+
+/// Highest rate `SnakeEventType::TickRate` may set
+pub const MAX_TICK_RATE: u32 = 60;
+
+/// What to do when simulation falls behind schedule
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LagPolicy {
+    /// Run missed ticks back to back, but no more than given amount at once
+    CatchUp(u32),
+    /// Drop missed ticks and continue from current time
+    Skip,
+}
+
+/// Fixed timestep scheduler.
+/// Keeps ticks aligned to absolute deadlines so time spent on simulation
+/// and sending doesn't stretch the tick period.
+pub struct Ticker {
+    rate: u32,
+    period: Duration,
+    next: Instant,
+    tick: u64,
+    policy: LagPolicy,
+}
+
+impl Ticker {
+    pub fn new(rate: u32, policy: LagPolicy) -> Self {
+        Self::starting_at(rate, policy, Instant::now())
+    }
+
+    /// Ticker whose first tick is due one period after `start`
+    pub fn starting_at(rate: u32, policy: LagPolicy, start: Instant) -> Self {
+        let period = Self::period_of(rate);
+        Self {
+            rate,
+            period,
+            next: start + period,
+            tick: 0,
+            policy,
+        }
+    }
+
+    fn period_of(rate: u32) -> Duration {
+        Duration::from_secs(1) / rate.max(1)
+    }
+
+    /// Ticks per second
+    pub fn rate(&self) -> u32 {
+        self.rate
+    }
+
+    /// Duration of one tick
+    pub fn period(&self) -> Duration {
+        self.period
+    }
+
+    /// Number of ticks passed since creation
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    /// When next tick is due
+    pub fn deadline(&self) -> Instant {
+        self.next
+    }
+
+    /// Change tick rate. New period applies starting from next tick.
+    pub fn set_rate(&mut self, rate: u32) {
+        if rate == self.rate {
+            return;
+        }
+        let old_period = self.period;
+        self.rate = rate;
+        self.period = Self::period_of(rate);
+        self.next = self.next - old_period + self.period;
+    }
+
+    /// Block until next tick is due.
+    /// Returns how many ticks should be simulated now.
+    pub fn wait(&mut self) -> u32 {
+        let now = Instant::now();
+        if now < self.next {
+            thread::sleep(self.next - now);
+        }
+        self.advance(Instant::now())
+    }
+
+    /// Move schedule forward assuming `now` is at or after the deadline.
+    /// Returns how many ticks should be simulated now.
+    pub fn advance(&mut self, now: Instant) -> u32 {
+        let late = now.saturating_duration_since(self.next);
+        let missed = (late.as_nanos() / self.period.as_nanos()) as u32;
+        let steps = match self.policy {
+            LagPolicy::CatchUp(max) => (missed + 1).min(max.max(1)),
+            LagPolicy::Skip => 1,
+        };
+        self.tick += steps as u64;
+        // Ticks that weren't simulated are dropped, deadlines stay aligned
+        self.next += self.period * (missed + 1);
+        steps
+    }
+}
@@ -0,0 +1,128 @@
+use crossterm::style::{Color, Colors};
+use rusty_ascii_graphics::{ColorDepth, Compositor, Layer, Rect, RenderBuffer, RenderChar};
+use rusty_snake::board::{Board, BoardStyle, DebugOverlay, NoEffects};
+use rusty_snake::camera::Camera;
+use rusty_snake::{Cell, Game, Snake};
+
+// 6x4 map with one snake bending around and one food
+fn game() -> Game {
+    let mut game = Game::new(6, 4, 0, 1, false);
+    let mut snake = Snake::new(3, 1, "player".to_string(), 0);
+    snake.body = vec![(3, 1), (2, 1), (1, 1), (1, 2), (2, 2), (3, 2), (4, 2)];
+    for &(x, y) in &snake.body {
+        game.cells.set_cell(x, y, Cell::Player(0));
+    }
+    game.snakes.push(snake);
+    game.food.push((5, 0));
+    game.cells.set_cell(5, 0, Cell::Food);
+    game
+}
+
+fn camera(game: &Game) -> Camera {
+    let mut camera = Camera::new();
+    camera.update(game.size, game.teleport, (20, 20), None, false);
+    camera
+}
+
+fn render(game: &Game, style: &BoardStyle) -> RenderBuffer {
+    let mut buffer = RenderBuffer::new(game.size.0 + 2, game.size.1 + 2);
+    buffer.draw(&Board::new(game, &camera(game), style, &NoEffects));
+    buffer
+}
+
+#[test]
+fn fancy_board_has_box_border_and_corner_glyphs() {
+    let buffer = render(&game(), &BoardStyle::default());
+    assert_eq!(
+        buffer.to_string(),
+        "\
+╔══════╗
+║     *║
+║ ┌─@  ║
+║ └─── ║
+║      ║
+╚══════╝
+"
+    );
+}
+
+#[test]
+fn plain_board_uses_ascii() {
+    let style = BoardStyle {
+        fancy: false,
+        ..BoardStyle::default()
+    };
+    let buffer = render(&game(), &style);
+    assert_eq!(
+        buffer.to_string(),
+        "\
+########
+#     *#
+# 00@  #
+# 0000 #
+#      #
+########
+"
+    );
+}
+
+#[test]
+fn unicode_food_glyph() {
+    let style = BoardStyle {
+        unicode: true,
+        ..BoardStyle::default()
+    };
+    let buffer = render(&game(), &style);
+    assert_eq!(buffer.get(6, 1).char, '※');
+}
+
+#[test]
+fn debug_overlay_colors_cells_by_owner() {
+    let game = game();
+    let camera = camera(&game);
+    let rect = Rect::new(0, 0, 8, 6);
+    let mut layers = Compositor::new();
+    // Overlay is added first but composited on top
+    layers
+        .add(Layer::new("debug", rect, 1))
+        .draw(&DebugOverlay::new(&game, &camera));
+    let style = BoardStyle::default();
+    layers
+        .add(Layer::new("board", rect, 0))
+        .draw(&Board::new(&game, &camera, &style, &NoEffects));
+    let mut buffer = RenderBuffer::new(8, 6);
+    buffer.draw(&layers);
+    // Nearest named colors, as 16 color terminal would show them
+    let background = |x, y| {
+        let bg = buffer.get(x, y).style.bg;
+        bg.map(|c| ColorDepth::Ansi16.downgrade(c))
+    };
+    assert_eq!(background(0, 0), Some(Color::Black));
+    assert_eq!(background(4, 2), Some(Color::DarkRed));
+    assert_eq!(background(6, 1), Some(Color::DarkMagenta));
+    assert_eq!(background(1, 1), Some(Color::DarkGreen));
+    // Glyphs under the overlay are kept
+    assert_eq!(buffer.get(4, 2).char, '@');
+    assert_eq!(buffer.get(4, 2).style.fg, Some(Color::Green));
+}
+
+#[test]
+fn snake_colors_follow_slot() {
+    let mut game = game();
+    game.snakes[0].color = 1;
+    let buffer = render(&game, &BoardStyle::default());
+    assert_eq!(buffer.get(4, 2).style.fg, Some(Color::Blue));
+    assert_eq!(buffer.get(3, 2).style.fg, Some(Color::Red));
+}
+
+#[test]
+fn ansi_export_resets_colors_at_row_end() {
+    let mut buffer = RenderBuffer::new(3, 2);
+    let red = Colors {
+        foreground: Some(Color::Red),
+        background: None,
+    };
+    buffer.put(1, 0, RenderChar::new('x', red));
+    assert_eq!(buffer.to_string(), " x \n   \n");
+    assert_eq!(buffer.to_ansi(), " \x1b[38;5;9mx\x1b[0m \n   \n");
+}
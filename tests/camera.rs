@@ -0,0 +1,70 @@
+use rusty_snake::camera::Camera;
+
+const MAP: (usize, usize) = (100, 50);
+// Dead zone margin is 5 cells across and 2 down for this view
+const VIEW: (usize, usize) = (20, 10);
+
+fn follow(camera: &mut Camera, wrap: bool, target: (isize, isize)) -> (isize, isize) {
+    camera.update(MAP, wrap, VIEW, Some(target), true);
+    (camera.x, camera.y)
+}
+
+#[test]
+fn target_inside_dead_zone_does_not_scroll() {
+    let mut camera = Camera::new();
+    assert_eq!(follow(&mut camera, false, (10, 5)), (0, 0));
+    for target in [(5, 2), (14, 7), (9, 4)] {
+        assert_eq!(follow(&mut camera, false, target), (0, 0));
+    }
+    assert_eq!((camera.width, camera.height), VIEW);
+}
+
+#[test]
+fn view_scrolls_by_overshoot() {
+    let mut camera = Camera::new();
+    follow(&mut camera, false, (10, 5));
+    // One past the right edge of dead zone
+    assert_eq!(follow(&mut camera, false, (15, 5)), (1, 0));
+    assert_eq!(follow(&mut camera, false, (18, 8)), (4, 1));
+    // Back inside, view stays
+    assert_eq!(follow(&mut camera, false, (12, 6)), (4, 1));
+    assert_eq!(follow(&mut camera, false, (8, 2)), (3, 0));
+}
+
+#[test]
+fn view_stops_at_map_edges_without_teleport() {
+    let mut camera = Camera::new();
+    assert_eq!(follow(&mut camera, false, (99, 49)), (80, 40));
+    assert_eq!(follow(&mut camera, false, (0, 0)), (0, 0));
+    // Whole map fits, nothing to follow
+    camera.update(MAP, false, (200, 100), Some((99, 49)), false);
+    assert_eq!((camera.x, camera.y), (0, 0));
+    assert_eq!((camera.width, camera.height), MAP);
+}
+
+#[test]
+fn view_wraps_with_teleport() {
+    let mut camera = Camera::new();
+    assert_eq!(follow(&mut camera, true, (1, 1)), (96, 49));
+    assert_eq!(camera.to_view((1, 1)), Some((5, 2)));
+    // Crossing the border keeps scrolling the short way
+    assert_eq!(follow(&mut camera, true, (99, 0)), (94, 48));
+    assert_eq!(camera.to_view((99, 0)), Some((5, 2)));
+    assert_eq!(camera.to_view((50, 25)), None);
+}
+
+#[test]
+fn view_positions_map_back_to_cells() {
+    for wrap in [false, true] {
+        let mut camera = Camera::new();
+        follow(&mut camera, wrap, (98, 1));
+        for x in 0..VIEW.0 as isize {
+            for y in 0..VIEW.1 as isize {
+                let cell = camera.to_map((x, y));
+                assert!(cell.0 >= 0 && cell.0 < MAP.0 as isize);
+                assert!(cell.1 >= 0 && cell.1 < MAP.1 as isize);
+                assert_eq!(camera.to_view(cell), Some((x, y)));
+            }
+        }
+    }
+}
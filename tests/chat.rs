@@ -0,0 +1,55 @@
+use std::thread;
+use std::time::Duration;
+
+use rusty_snake::chat::{censor, ChatError, ChatRelay};
+
+#[test]
+fn burst_over_limit_is_refused_inside_window() {
+    let mut relay = ChatRelay::new(100, 2, Duration::from_secs(60));
+    assert!(relay.check("ann", "one").is_ok());
+    assert!(relay.check("ann", "two").is_ok());
+    assert_eq!(relay.check("ann", "three"), Err(ChatError::RateLimited));
+    // Limit is per author
+    assert!(relay.check("bob", "hi").is_ok());
+    relay.forget("ann");
+    assert!(relay.check("ann", "back").is_ok());
+}
+
+#[test]
+fn limit_resets_after_window() {
+    let mut relay = ChatRelay::new(100, 1, Duration::from_millis(20));
+    assert!(relay.check("ann", "one").is_ok());
+    assert_eq!(relay.check("ann", "two"), Err(ChatError::RateLimited));
+    thread::sleep(Duration::from_millis(30));
+    assert!(relay.check("ann", "three").is_ok());
+}
+
+#[test]
+fn long_messages_are_cut_at_max_length() {
+    let mut relay = ChatRelay::new(5, 10, Duration::from_secs(60));
+    assert_eq!(relay.check("ann", "abcdefgh").unwrap(), "abcde");
+    // Length counts chars, not bytes
+    assert_eq!(relay.check("ann", "ééééééé").unwrap(), "ééééé");
+    assert_eq!(relay.check("ann", "a\u{7}b\nc").unwrap(), "abc");
+    assert_eq!(relay.check("ann", "   "), Err(ChatError::Empty));
+}
+
+#[test]
+fn blocked_words_are_masked() {
+    let mut relay = ChatRelay::default();
+    relay.add_hook(censor(vec!["Darn".to_string(), "heck".to_string()]));
+    assert_eq!(
+        relay.check("ann", "darn it, HECK! darned").unwrap(),
+        "**** it, ****! darned"
+    );
+}
+
+#[test]
+fn hook_can_drop_message() {
+    let mut relay = ChatRelay::default();
+    relay.add_hook(Box::new(|author, text| {
+        (author != "spammer").then(|| text.to_string())
+    }));
+    assert_eq!(relay.check("spammer", "buy"), Err(ChatError::Blocked));
+    assert!(relay.check("ann", "hello").is_ok());
+}
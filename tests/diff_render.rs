@@ -0,0 +1,47 @@
+use crossterm::style::{Color, Colors};
+use rusty_ascii_graphics::{ColorDepth, RectangleShape, RenderBuffer, RenderChar};
+
+fn green() -> Colors {
+    Colors {
+        foreground: Some(Color::Rgb { r: 0, g: 255, b: 0 }),
+        background: None,
+    }
+}
+
+fn render(buffer: &mut RenderBuffer) -> String {
+    let mut out = Vec::new();
+    buffer.render_to(&mut out);
+    String::from_utf8(out).unwrap()
+}
+
+fn scene() -> RenderBuffer {
+    let mut buffer = RenderBuffer::new(10, 5);
+    buffer.set_color_depth(ColorDepth::TrueColor);
+    let border = RenderChar::new('#', green());
+    buffer.draw(&RectangleShape::new(0, 0, 10, 5, border, false));
+    buffer.put(3, 2, RenderChar::new('o', green()));
+    buffer
+}
+
+#[test]
+fn unchanged_frame_writes_nothing() {
+    let mut buffer = scene();
+    assert!(!render(&mut buffer).is_empty());
+    assert_eq!(render(&mut buffer), "");
+}
+
+#[test]
+fn changed_cell_writes_one_move_and_glyph() {
+    let mut buffer = scene();
+    render(&mut buffer);
+    buffer.put(3, 2, RenderChar::new('@', green()));
+    assert_eq!(render(&mut buffer), "\x1b[3;4H\x1b[38;2;0;255;0m@\x1b[0m");
+}
+
+#[test]
+fn invalidate_redraws_every_cell() {
+    let mut buffer = scene();
+    let first = render(&mut buffer);
+    buffer.invalidate();
+    assert_eq!(render(&mut buffer), first);
+}
@@ -0,0 +1,103 @@
+use crossterm::style::Color;
+use rusty_ascii_graphics::{
+    graphemes, split_width, str_width, RenderBuffer, RenderChar, Span, Style, StyledText,
+};
+
+fn put_str(buffer: &mut RenderBuffer, x: isize, y: isize, text: &str) {
+    let mut x = x;
+    for g in graphemes(text) {
+        let c = RenderChar::grapheme(g, Style::default());
+        let width = c.width() as isize;
+        buffer.put(x, y, c);
+        x += width;
+    }
+}
+
+#[test]
+fn clusters_keep_marks_and_sequences_together() {
+    let text = "e\u{301}👍🏽👨\u{200D}👩\u{200D}👧🇫🇷🇩🇪❤\u{FE0F}a\n";
+    let clusters: Vec<&str> = graphemes(text).collect();
+    assert_eq!(
+        clusters,
+        [
+            "e\u{301}",
+            "👍🏽",
+            "👨\u{200D}👩\u{200D}👧",
+            "🇫🇷",
+            "🇩🇪",
+            "❤\u{FE0F}",
+            "a",
+            "\n"
+        ]
+    );
+    assert_eq!(str_width("e\u{301}👍🏽🇫🇷❤\u{FE0F}"), 7);
+}
+
+#[test]
+fn wide_glyph_takes_two_cells() {
+    let mut buffer = RenderBuffer::new(6, 1);
+    put_str(&mut buffer, 0, 0, "a🍎b");
+    assert!(buffer.get(2, 0).is_continuation());
+    assert_eq!(buffer.get(3, 0).char, 'b');
+    assert_eq!(buffer.to_string(), "a🍎b  \n");
+}
+
+#[test]
+fn overwriting_half_of_wide_glyph_clears_other_half() {
+    let mut buffer = RenderBuffer::new(4, 2);
+    put_str(&mut buffer, 0, 0, "🍎🍎");
+    put_str(&mut buffer, 1, 0, "x");
+    put_str(&mut buffer, 0, 1, "🍎🍎");
+    put_str(&mut buffer, 1, 1, "🍌");
+    assert_eq!(buffer.to_string(), " x🍎\n 🍌 \n");
+}
+
+#[test]
+fn wide_glyph_at_right_edge_becomes_space() {
+    let mut buffer = RenderBuffer::new(3, 1);
+    put_str(&mut buffer, 0, 0, "ab🍎");
+    assert_eq!(buffer.to_string(), "ab \n");
+}
+
+#[test]
+fn combining_marks_are_written_with_their_char() {
+    let mut buffer = RenderBuffer::new(3, 1);
+    let red = Style::new().fg(Color::Red);
+    buffer.put(1, 0, RenderChar::grapheme("e\u{301}", red));
+    assert_eq!(buffer.get(1, 0).width(), 1);
+    assert_eq!(buffer.to_ansi(), " \x1b[38;5;9me\u{301}\x1b[0m \n");
+}
+
+#[test]
+fn changed_wide_glyph_is_rendered_once() {
+    let mut buffer = RenderBuffer::new(4, 1);
+    let mut out = Vec::new();
+    buffer.render_to(&mut out);
+    put_str(&mut buffer, 1, 0, "🍎");
+    let mut out = Vec::new();
+    buffer.render_to(&mut out);
+    assert_eq!(String::from_utf8(out).unwrap(), "\x1b[1;2H🍎");
+}
+
+#[test]
+fn text_wraps_by_columns_of_graphemes() {
+    let text = StyledText::new(0, 0, 4, 2, vec![Span::new("🍎🍌🍒")]).wrap(true);
+    let mut buffer = RenderBuffer::new(4, 2);
+    buffer.draw(&text);
+    assert_eq!(buffer.to_string(), "🍎🍌\n🍒  \n");
+}
+
+#[test]
+fn text_splits_by_columns() {
+    assert_eq!(split_width("abcdefg", 3), vec!["abc", "def", "g"]);
+    // Wide glyph moves to next piece instead of overflowing
+    assert_eq!(split_width("ab漢字c", 3), vec!["ab", "漢", "字c"]);
+    // Marks stay with their char
+    assert_eq!(
+        split_width("e\u{301}e\u{301}e\u{301}", 2),
+        vec!["e\u{301}e\u{301}", "e\u{301}"]
+    );
+    // Too wide for any piece, still shown on its own
+    assert_eq!(split_width("a👍b", 1), vec!["a", "👍", "b"]);
+    assert!(split_width("", 4).is_empty());
+}
@@ -0,0 +1,93 @@
+use rusty_snake::{Direction, Game, SnakeEventType};
+
+fn game_with_player() -> Game {
+    let mut game = Game::new(20, 20, 0, 1, true);
+    game.add_player("player".to_string());
+    game
+}
+
+fn turn(game: &mut Game, dir: Direction) {
+    game.handle_events(SnakeEventType::Movement(dir), "player".to_string());
+}
+
+fn head(game: &Game) -> (isize, isize) {
+    game.get_snake("player".to_string()).unwrap().head
+}
+
+fn wrap(game: &Game, pos: (isize, isize)) -> (isize, isize) {
+    (
+        (pos.0 + game.size.0 as isize) % game.size.0 as isize,
+        (pos.1 + game.size.1 as isize) % game.size.1 as isize,
+    )
+}
+
+#[test]
+fn turns_within_one_tick_are_applied_in_order() {
+    let mut game = game_with_player();
+    turn(&mut game, Direction::Up);
+    game.step();
+
+    // U-turn: left then down before the next step
+    let start = head(&game);
+    turn(&mut game, Direction::Left);
+    turn(&mut game, Direction::Down);
+    game.step();
+    assert_eq!(head(&game), wrap(&game, (start.0 - 1, start.1)));
+    game.step();
+    assert_eq!(head(&game), wrap(&game, (start.0 - 1, start.1 + 1)));
+    assert_eq!(
+        game.get_snake("player".to_string()).unwrap().direction,
+        Direction::Down
+    );
+}
+
+#[test]
+fn reversal_is_rejected() {
+    let mut game = game_with_player();
+    turn(&mut game, Direction::Up);
+    game.step();
+
+    let start = head(&game);
+    turn(&mut game, Direction::Down);
+    game.step();
+    assert_eq!(head(&game), wrap(&game, (start.0, start.1 - 1)));
+}
+
+#[test]
+fn reversal_of_queued_turn_is_rejected() {
+    let mut game = game_with_player();
+    turn(&mut game, Direction::Up);
+    game.step();
+
+    turn(&mut game, Direction::Left);
+    turn(&mut game, Direction::Right);
+    let snake = game.get_snake("player".to_string()).unwrap();
+    assert_eq!(snake.queued, [Direction::Left]);
+}
+
+#[test]
+fn queue_is_limited_by_input_buffer() {
+    let mut game = game_with_player();
+    game.input_buffer = 2;
+    turn(&mut game, Direction::Up);
+    turn(&mut game, Direction::Left);
+    turn(&mut game, Direction::Down);
+    let snake = game.get_snake("player".to_string()).unwrap();
+    assert_eq!(snake.queued, [Direction::Up, Direction::Left]);
+}
+
+#[test]
+fn stop_clears_queue() {
+    let mut game = game_with_player();
+    turn(&mut game, Direction::Up);
+    turn(&mut game, Direction::Left);
+    turn(&mut game, Direction::Stop);
+    let start = head(&game);
+    game.step();
+    assert_eq!(head(&game), start);
+    assert!(game
+        .get_snake("player".to_string())
+        .unwrap()
+        .queued
+        .is_empty());
+}
@@ -0,0 +1,123 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use rusty_snake::Direction;
+
+#[allow(dead_code)]
+#[path = "../src/bin/client/keys.rs"]
+mod keys;
+
+use keys::{format_key, parse_key, Action, KeyBindings, KeySet};
+
+fn press(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+    KeyEvent::new(code, modifiers)
+}
+
+fn table(s: &str) -> toml::value::Table {
+    toml::from_str(s).unwrap()
+}
+
+#[test]
+fn keys_round_trip_through_config_names() {
+    for name in [
+        "w",
+        "Up",
+        "Left",
+        "Enter",
+        "Esc",
+        "Tab",
+        "Backspace",
+        "Space",
+        "F1",
+        "F12",
+        "ctrl+c",
+        "alt+x",
+        "ctrl+alt+Down",
+        "shift+Tab",
+        "ctrl++",
+    ] {
+        let key = parse_key(name).unwrap();
+        assert_eq!(format_key(&key), name);
+        assert_eq!(parse_key(&format_key(&key)), Some(key));
+    }
+    // Names are case insensitive, output is canonical
+    assert_eq!(format_key(&parse_key("CTRL+up").unwrap()), "ctrl+Up");
+    // Shift is part of typed char
+    assert_eq!(parse_key("shift+a"), parse_key("a"));
+}
+
+#[test]
+fn shift_tab_is_back_tab() {
+    let key = parse_key("shift+Tab").unwrap();
+    assert_eq!(key, (KeyCode::BackTab, KeyModifiers::NONE));
+    assert_eq!(format_key(&key), "shift+Tab");
+    // Terminal sends BackTab with shift held
+    let mut bindings = KeyBindings::new(&[KeySet::Wasd]);
+    bindings.load(&table(r#""shift+Tab" = "quit""#));
+    assert_eq!(
+        bindings.action(&press(KeyCode::BackTab, KeyModifiers::SHIFT)),
+        Some(&Action::Quit)
+    );
+    assert_eq!(
+        bindings.action(&press(KeyCode::Tab, KeyModifiers::NONE)),
+        None
+    );
+}
+
+#[test]
+fn bad_key_names_are_rejected() {
+    for name in ["", "meta+a", "Fx", "Home2", "ctrl+"] {
+        assert_eq!(parse_key(name), None, "{:?}", name);
+    }
+}
+
+#[test]
+fn config_rebinds_and_reports_errors() {
+    let mut bindings = KeyBindings::new(&[KeySet::Wasd]);
+    let errors = bindings.load(&table(
+        r#"
+        "x" = "quit"
+        "p" = "none"
+        "hyper+z" = "quit"
+        "y" = "fly"
+        "z" = 3
+        "#,
+    ));
+    assert_eq!(errors.len(), 3);
+    assert!(errors.iter().any(|e| e.contains("hyper+z")));
+    assert!(errors.iter().any(|e| e.contains("fly")));
+
+    let none = KeyModifiers::NONE;
+    assert_eq!(
+        bindings.action(&press(KeyCode::Char('x'), none)),
+        Some(&Action::Quit)
+    );
+    assert_eq!(bindings.action(&press(KeyCode::Char('p'), none)), None);
+    assert_eq!(bindings.action(&press(KeyCode::Char('y'), none)), None);
+    // Keys not mentioned keep their defaults
+    assert_eq!(
+        bindings.action(&press(KeyCode::Char('w'), none)),
+        Some(&Action::Move(0, Direction::Up))
+    );
+    assert_eq!(
+        bindings.action(&press(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+        Some(&Action::Quit)
+    );
+    assert_eq!(
+        bindings.action(&press(KeyCode::Char('g'), none)),
+        Some(&Action::Record)
+    );
+}
+
+#[test]
+fn second_player_gets_own_keys() {
+    let bindings = KeyBindings::new(&[KeySet::Wasd, KeySet::Arrows]);
+    let none = KeyModifiers::NONE;
+    assert_eq!(
+        bindings.action(&press(KeyCode::Left, none)),
+        Some(&Action::Move(1, Direction::Left))
+    );
+    assert_eq!(
+        Action::from_name("left:2"),
+        Some(Action::Move(1, Direction::Left))
+    );
+    assert_eq!(Action::from_name("left:0"), None);
+}
@@ -0,0 +1,81 @@
+use crossterm::style::{Color, Colors};
+use rusty_ascii_graphics::{
+    Compositor, Layer, Rect, RectangleShape, RenderBuffer, RenderChar, Span, StyledText,
+};
+
+fn fill(c: char) -> RectangleShape {
+    let colors = Colors {
+        foreground: None,
+        background: None,
+    };
+    RectangleShape::new(0, 0, 100, 100, RenderChar::new(c, colors), true)
+}
+
+#[test]
+fn layers_composite_in_z_order() {
+    let mut layers = Compositor::new();
+    layers
+        .add(Layer::new("top", Rect::new(1, 0, 2, 2), 2))
+        .draw(&fill('t'));
+    layers
+        .add(Layer::new("bottom", Rect::new(0, 0, 4, 2), 0))
+        .draw(&fill('b'));
+    layers
+        .add(Layer::new("middle", Rect::new(2, 1, 2, 1), 1))
+        .draw(&fill('m'));
+    let mut buffer = RenderBuffer::new(5, 2);
+    buffer.draw(&layers);
+    assert_eq!(buffer.to_string(), "bttb \nbttm \n");
+}
+
+#[test]
+fn transparent_cells_keep_what_is_below() {
+    let mut layers = Compositor::new();
+    layers
+        .add(Layer::new("base", Rect::new(0, 0, 6, 1), 0))
+        .draw(&fill('.'));
+    let label = StyledText::new(0, 0, 6, 1, vec![Span::new("ab")]);
+    layers
+        .add(Layer::new("label", Rect::new(2, 0, 4, 1), 1))
+        .draw(&label);
+    let mut tint = RenderChar::transparent();
+    tint.style.bg = Some(Color::Blue);
+    layers
+        .add(Layer::new("tint", Rect::new(0, 0, 1, 1), 2))
+        .draw(&RectangleShape::new(0, 0, 1, 1, tint, true));
+
+    let mut buffer = RenderBuffer::new(6, 1);
+    buffer.draw(&layers);
+    assert_eq!(buffer.to_string(), "..ab..\n");
+    assert_eq!(buffer.get(0, 0).char, '.');
+    assert_eq!(buffer.get(0, 0).style.bg, Some(Color::Blue));
+}
+
+#[test]
+fn clip_limits_what_layer_changes() {
+    let mut layers = Compositor::new();
+    let layer = Layer::new("clipped", Rect::new(0, 0, 4, 3), 0).clip(Rect::new(1, 1, 2, 1));
+    layers.add(layer).draw(&fill('x'));
+    let mut buffer = RenderBuffer::new(4, 3);
+    buffer.draw(&layers);
+    assert_eq!(buffer.to_string(), "    \n xx \n    \n");
+}
+
+#[test]
+fn hit_finds_topmost_drawn_layer() {
+    let mut layers = Compositor::new();
+    layers
+        .add(Layer::new("back", Rect::new(0, 0, 10, 5), 0))
+        .draw(&fill('b'));
+    let button = StyledText::new(0, 0, 4, 1, vec![Span::new("OK")]);
+    layers
+        .add(Layer::new("button", Rect::new(3, 2, 4, 1), 1))
+        .draw(&button);
+
+    let (layer, pos) = layers.hit(4, 2).unwrap();
+    assert_eq!((layer.name, pos), ("button", (1, 0)));
+    // Part of button layer nothing was drawn on
+    let (layer, pos) = layers.hit(6, 2).unwrap();
+    assert_eq!((layer.name, pos), ("back", (6, 2)));
+    assert!(layers.hit(10, 0).is_none());
+}
@@ -0,0 +1,86 @@
+use std::time::Duration;
+
+use crossterm::style::Color;
+use rusty_ascii_graphics::{
+    Rasterizer, Recording, RenderBuffer, RenderChar, Style, CELL_HEIGHT, CELL_WIDTH,
+};
+
+const RED: (u8, u8, u8) = (255, 0, 0);
+const BLUE: (u8, u8, u8) = (92, 92, 255);
+
+fn buffer(text: &str, style: Style) -> RenderBuffer {
+    let mut buffer = RenderBuffer::new(text.chars().count(), 1);
+    for (x, c) in text.chars().enumerate() {
+        buffer.put(x as isize, 0, RenderChar::styled(c, style));
+    }
+    buffer
+}
+
+#[test]
+fn cells_are_drawn_with_font_and_colors() {
+    let style = Style::new().fg(Color::Red).bg(Color::Blue);
+    let image = Rasterizer::new().image(&buffer("A ", style));
+    assert_eq!((image.width, image.height), (2 * CELL_WIDTH, CELL_HEIGHT));
+    // Top of `A` is two pixels in the middle, rows are doubled
+    assert_eq!(image.pixel(2, 0), RED);
+    assert_eq!(image.pixel(2, 1), RED);
+    assert_eq!(image.pixel(1, 0), BLUE);
+    assert_eq!(image.pixel(CELL_WIDTH + 2, 0), BLUE);
+}
+
+#[test]
+fn box_corners_join_without_stubs() {
+    let scale = 2;
+    let image = Rasterizer::new()
+        .scale(scale)
+        .image(&buffer("┌─", Style::new()));
+    let lit = |x: usize, y: usize| image.pixel(x * scale, y * scale) != (0, 0, 0);
+    assert!(lit(3, 7) && lit(3, CELL_HEIGHT - 1) && lit(CELL_WIDTH - 1, 7));
+    assert!(!lit(3, 6) && !lit(2, 7));
+    assert!((0..CELL_WIDTH).all(|x| lit(CELL_WIDTH + x, 7)));
+}
+
+#[test]
+fn screenshot_is_png() {
+    let mut out = Vec::new();
+    let image = Rasterizer::new().image(&buffer("snake", Style::new().fg(Color::Green)));
+    image.write_png(&mut out).unwrap();
+    let mut reader = png::Decoder::new(out.as_slice()).read_info().unwrap();
+    let mut data = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut data).unwrap();
+    assert_eq!(
+        (info.width, info.height),
+        (5 * CELL_WIDTH as u32, CELL_HEIGHT as u32)
+    );
+    assert_eq!(&data[..info.buffer_size()], image.data.as_slice());
+}
+
+#[test]
+fn recording_skips_repeated_frames() {
+    let raster = Rasterizer::new();
+    let mut recording = Recording::new();
+    let ms = Duration::from_millis;
+    recording.push(&buffer("@  ", Style::new()), ms(0));
+    recording.push(&buffer(" @ ", Style::new()), ms(100));
+    recording.push(&buffer(" @ ", Style::new()), ms(200));
+    recording.push(&buffer("  @", Style::new()), ms(300));
+    assert_eq!(recording.len(), 3);
+    assert_eq!(recording.cells(), 9);
+
+    let mut out = Vec::new();
+    recording.write_gif(&raster, &mut out).unwrap();
+    let mut options = gif::DecodeOptions::new();
+    options.set_color_output(gif::ColorOutput::RGBA);
+    let mut decoder = options.read_info(out.as_slice()).unwrap();
+    let mut delays = Vec::new();
+    while let Some(frame) = decoder.read_next_frame().unwrap() {
+        delays.push(frame.delay);
+    }
+    // In hundredths of second, last frame is held before looping
+    assert_eq!(delays, vec![10, 20, 100]);
+
+    let mut out = Vec::new();
+    recording.write_apng(&raster, &mut out).unwrap();
+    let reader = png::Decoder::new(out.as_slice()).read_info().unwrap();
+    assert_eq!(reader.info().animation_control().unwrap().num_frames, 3);
+}
@@ -0,0 +1,112 @@
+use crossterm::style::Color;
+use rusty_ascii_graphics::{PixelGrid, RenderBuffer, Sample, ScaleMode, Scaled};
+
+/// Grid of weighted pixels, empty elsewhere
+struct Pattern {
+    size: (usize, usize),
+    pixels: Vec<((usize, usize), Color, u32)>,
+}
+
+impl Pattern {
+    fn new(width: usize, height: usize) -> Self {
+        Pattern {
+            size: (width, height),
+            pixels: Vec::new(),
+        }
+    }
+
+    fn set(mut self, pos: (usize, usize), color: Color, weight: u32) -> Self {
+        self.pixels.push((pos, color, weight));
+        self
+    }
+}
+
+impl PixelGrid for Pattern {
+    fn size(&self) -> (usize, usize) {
+        self.size
+    }
+
+    fn sample(&self, x: usize, y: usize) -> Option<Sample> {
+        self.pixels
+            .iter()
+            .find(|(pos, _, _)| *pos == (x, y))
+            .map(|&(_, color, weight)| Sample { color, weight })
+    }
+}
+
+fn scaled(grid: &Pattern, size: (usize, usize), mode: ScaleMode) -> RenderBuffer {
+    let mut buffer = RenderBuffer::new(size.0, size.1);
+    buffer.draw(&Scaled::new(0, 0, size.0, size.1, mode, grid));
+    buffer
+}
+
+fn colors(buffer: &RenderBuffer, x: isize, y: isize) -> (Option<Color>, Option<Color>) {
+    let c = buffer.get(x, y);
+    (c.style.fg, c.style.bg)
+}
+
+#[test]
+fn half_blocks_pack_two_pixels_per_char() {
+    // One column of pixels per char, top and bottom of each
+    let grid = Pattern::new(5, 2)
+        .set((1, 0), Color::Red, 1)
+        .set((2, 1), Color::Red, 1)
+        .set((3, 0), Color::Red, 1)
+        .set((3, 1), Color::Red, 1)
+        .set((4, 0), Color::Red, 1)
+        .set((4, 1), Color::Blue, 1);
+    let buffer = scaled(&grid, (5, 1), ScaleMode::HalfBlock);
+    assert_eq!(buffer.to_string(), " ▀▄█▀\n");
+    assert_eq!(colors(&buffer, 1, 0), (Some(Color::Red), None));
+    assert_eq!(colors(&buffer, 2, 0), (Some(Color::Red), None));
+    assert_eq!(colors(&buffer, 3, 0), (Some(Color::Red), None));
+    // Two colors, bottom one goes to background
+    assert_eq!(colors(&buffer, 4, 0), (Some(Color::Red), Some(Color::Blue)));
+}
+
+#[test]
+fn braille_packs_eight_dots_per_char() {
+    let grid = Pattern::new(4, 4)
+        .set((0, 0), Color::Red, 1)
+        .set((1, 3), Color::Red, 1)
+        .set((2, 1), Color::Green, 1)
+        .set((2, 2), Color::Green, 1)
+        .set((3, 0), Color::Blue, 1);
+    let buffer = scaled(&grid, (2, 1), ScaleMode::Braille);
+    // Dots 1 and 8, then dots 2, 3 and 4
+    assert_eq!(buffer.to_string(), "⢁⠎\n");
+    assert_eq!(colors(&buffer, 0, 0), (Some(Color::Red), None));
+    // Char takes color of most dots
+    assert_eq!(colors(&buffer, 1, 0), (Some(Color::Green), None));
+}
+
+#[test]
+fn downscaling_keeps_heaviest_color() {
+    // Each pixel covers 2x2 cells
+    let grid = Pattern::new(4, 4)
+        .set((0, 0), Color::Red, 1)
+        .set((1, 0), Color::Red, 1)
+        .set((0, 1), Color::Red, 1)
+        .set((1, 1), Color::Green, 16)
+        .set((2, 0), Color::Red, 1)
+        .set((3, 0), Color::Blue, 1)
+        .set((3, 1), Color::Blue, 1)
+        .set((2, 3), Color::Red, 1);
+    let buffer = scaled(&grid, (2, 1), ScaleMode::HalfBlock);
+    assert_eq!(buffer.to_string(), "▀▀\n");
+    // Single heavy cell outweighs three light ones
+    assert_eq!(colors(&buffer, 0, 0), (Some(Color::Green), None));
+    // Summed weight of two blue cells beats one red
+    assert_eq!(colors(&buffer, 1, 0), (Some(Color::Blue), Some(Color::Red)));
+}
+
+#[test]
+fn upscaling_repeats_cells() {
+    let grid = Pattern::new(2, 1)
+        .set((0, 0), Color::Red, 1)
+        .set((1, 0), Color::Blue, 1);
+    let buffer = scaled(&grid, (4, 1), ScaleMode::HalfBlock);
+    assert_eq!(buffer.to_string(), "████\n");
+    assert_eq!(colors(&buffer, 1, 0), (Some(Color::Red), None));
+    assert_eq!(colors(&buffer, 2, 0), (Some(Color::Blue), None));
+}
@@ -0,0 +1,76 @@
+use crossterm::style::Colors;
+use rusty_ascii_graphics::{
+    BorderStyle, BoxShape, Drawable, Ellipse, Line, Polyline, RenderBuffer, RenderChar,
+};
+
+const NONE: Colors = Colors {
+    foreground: None,
+    background: None,
+};
+
+fn render(width: usize, height: usize, shapes: &[&dyn Drawable]) -> String {
+    let mut buffer = RenderBuffer::new(width, height);
+    for shape in shapes {
+        shape.draw(&mut buffer);
+    }
+    buffer.to_string()
+}
+
+#[test]
+fn line_steps_diagonally() {
+    let line = Line::new((0, 0), (5, 2), RenderChar::new('*', NONE));
+    assert_eq!(render(6, 3, &[&line]), "**    \n  **  \n    **\n");
+}
+
+#[test]
+fn box_styles() {
+    let single = BoxShape::new(0, 0, 3, 3, BorderStyle::Single, NONE);
+    let rounded = BoxShape::new(3, 0, 3, 3, BorderStyle::Rounded, NONE);
+    let ascii = BoxShape::new(6, 0, 3, 3, BorderStyle::Ascii, NONE);
+    assert_eq!(
+        render(9, 3, &[&single, &rounded, &ascii]),
+        "┌─┐╭─╮+-+\n│ ││ │| |\n└─┘╰─╯+-+\n"
+    );
+}
+
+#[test]
+fn boxes_sharing_edges_join() {
+    let left = BoxShape::new(0, 0, 4, 3, BorderStyle::Double, NONE);
+    let right = BoxShape::new(3, 0, 4, 3, BorderStyle::Double, NONE);
+    let below = BoxShape::new(0, 2, 7, 3, BorderStyle::Double, NONE);
+    assert_eq!(
+        render(7, 5, &[&left, &right, &below]),
+        "╔══╦══╗\n║  ║  ║\n╠══╩══╣\n║     ║\n╚═════╝\n"
+    );
+}
+
+#[test]
+fn filled_box_clears_inside() {
+    let mut buffer = RenderBuffer::new(4, 3);
+    buffer.clear(RenderChar::new('x', NONE));
+    let fill = RenderChar::new(' ', NONE);
+    buffer.draw(&BoxShape::new(0, 0, 4, 3, BorderStyle::Single, NONE).fill(fill));
+    assert_eq!(buffer.to_string(), "┌──┐\n│  │\n└──┘\n");
+}
+
+#[test]
+fn circle_outline_has_no_gaps() {
+    let circle = Ellipse::circle(4, 2, 2, RenderChar::new('o', NONE), false);
+    assert_eq!(
+        render(9, 5, &[&circle]),
+        "  ooooo  \noo     oo\no       o\noo     oo\n  ooooo  \n"
+    );
+}
+
+#[test]
+fn polyline_picks_corners_and_crossings() {
+    let points = [(0, 1), (4, 1), (4, 3), (2, 3), (2, 0)];
+    let line = Polyline::new(&points, BorderStyle::Single, NONE);
+    assert_eq!(render(5, 4, &[&line]), "  │  \n──┼─┐\n  │ │\n  └─┘\n");
+}
+
+#[test]
+fn slanted_polyline_becomes_stairs() {
+    let line = Polyline::new(&[(0, 0), (3, 3)], BorderStyle::Single, NONE);
+    assert_eq!(render(4, 4, &[&line]), "─┐  \n └┐ \n  └┐\n   │\n");
+}
@@ -0,0 +1,84 @@
+use rusty_ascii_graphics::{Compositor, Layer, Rect};
+use rusty_snake::board::{steer, Board, BoardStyle, NoEffects};
+use rusty_snake::camera::Camera;
+use rusty_snake::{Direction, Game, Snake, SnakeEventType};
+
+fn snake_at(x: isize, y: isize) -> Snake {
+    Snake::new(x, y, "player".to_string(), 0)
+}
+
+#[test]
+fn turns_along_longer_axis() {
+    let game = Game::new(20, 20, 0, 1, false);
+    let snake = snake_at(5, 5);
+    assert_eq!(
+        game.direction_toward(&snake, (6, 15)),
+        Some(Direction::Down)
+    );
+    assert_eq!(game.direction_toward(&snake, (1, 4)), Some(Direction::Left));
+    assert_eq!(game.direction_toward(&snake, (5, 5)), None);
+}
+
+#[test]
+fn takes_shorter_way_across_edge_when_wrapping() {
+    let snake = snake_at(1, 5);
+    let wrapping = Game::new(20, 20, 0, 1, true);
+    assert_eq!(
+        wrapping.direction_toward(&snake, (18, 5)),
+        Some(Direction::Left)
+    );
+    let walled = Game::new(20, 20, 0, 1, false);
+    assert_eq!(
+        walled.direction_toward(&snake, (18, 5)),
+        Some(Direction::Right)
+    );
+}
+
+#[test]
+fn never_turns_back() {
+    let game = Game::new(20, 20, 0, 1, false);
+    let mut snake = snake_at(5, 5);
+    // Moving right, target behind and a bit below
+    snake.direction = Direction::Right;
+    snake.moved_from = Direction::Left;
+    assert_eq!(game.direction_toward(&snake, (0, 7)), Some(Direction::Down));
+    // Straight behind, nothing to turn to
+    assert_eq!(game.direction_toward(&snake, (0, 5)), None);
+}
+
+#[test]
+fn click_on_empty_board_cell_steers() {
+    let mut game = Game::new(20, 10, 0, 1, false);
+    game.add_player("player".to_string());
+    let head = game.snakes[0].head;
+    let mut camera = Camera::new();
+    camera.update(game.size, game.teleport, (20, 10), None, false);
+
+    let mut layers = Compositor::new();
+    let style = BoardStyle::default();
+    layers
+        .add(Layer::new("board", Rect::new(0, 0, 22, 12), 0).solid())
+        .draw(&Board::new(&game, &camera, &style, &NoEffects));
+    // Empty cell far from the head along one axis, border adds one
+    let target = ((head.0 + 10) % 20, head.1);
+    let (x, y) = (target.0 + 1, target.1 + 1);
+    assert!(layers
+        .get("board")
+        .unwrap()
+        .buffer
+        .get(x, y)
+        .is_transparent());
+
+    let (layer, pos) = layers.hit(x, y).unwrap();
+    assert_eq!(layer.name, "board");
+    let event = steer(&game, &camera, "player", pos).unwrap();
+    assert_eq!(event.event_owner, "player");
+    let expected = if target.0 > head.0 {
+        Direction::Right
+    } else {
+        Direction::Left
+    };
+    assert!(matches!(event.event_type, SnakeEventType::Movement(dir) if dir == expected));
+    // Border is not part of the map
+    assert!(steer(&game, &camera, "player", (0, y)).is_none());
+}
@@ -0,0 +1,96 @@
+use crossterm::style::Color;
+use rusty_ascii_graphics::{ColorDepth, RenderBuffer, RenderChar, Style};
+
+fn ansi(cells: &[(char, Style)], depth: ColorDepth) -> String {
+    let mut buffer = RenderBuffer::new(cells.len(), 1);
+    buffer.set_color_depth(depth);
+    for (x, &(c, style)) in cells.iter().enumerate() {
+        buffer.put(x as isize, 0, RenderChar::styled(c, style));
+    }
+    buffer.to_ansi()
+}
+
+#[test]
+fn rgb_downgrades_to_nearest_color() {
+    let orange = Color::Rgb {
+        r: 255,
+        g: 135,
+        b: 0,
+    };
+    assert_eq!(ColorDepth::TrueColor.downgrade(orange), orange);
+    assert_eq!(ColorDepth::Ansi256.downgrade(orange), Color::AnsiValue(208));
+    assert_eq!(ColorDepth::Ansi16.downgrade(orange), Color::DarkYellow);
+    let gray = Color::Rgb {
+        r: 100,
+        g: 100,
+        b: 100,
+    };
+    assert_eq!(ColorDepth::Ansi256.downgrade(gray), Color::AnsiValue(241));
+    // Named colors are shown by every terminal
+    assert_eq!(ColorDepth::Ansi16.downgrade(Color::Red), Color::Red);
+    assert_eq!(
+        ColorDepth::Ansi16.downgrade(Color::AnsiValue(9)),
+        Color::Red
+    );
+}
+
+#[test]
+fn buffer_writes_colors_of_its_depth() {
+    let style = Style::new().fg(Color::Rgb { r: 0, g: 0, b: 255 });
+    assert_eq!(
+        ansi(&[('x', style)], ColorDepth::TrueColor),
+        "\x1b[38;2;0;0;255mx\x1b[0m\n"
+    );
+    assert_eq!(
+        ansi(&[('x', style)], ColorDepth::Ansi256),
+        "\x1b[38;5;21mx\x1b[0m\n"
+    );
+    assert_eq!(
+        ansi(&[('x', style)], ColorDepth::Ansi16),
+        "\x1b[34mx\x1b[0m\n"
+    );
+}
+
+#[test]
+fn ansi16_uses_sgr_color_codes() {
+    let cells = [
+        ('a', Style::new().fg(Color::DarkRed).bg(Color::Black)),
+        ('b', Style::new().fg(Color::Red).bg(Color::White)),
+    ];
+    assert_eq!(
+        ansi(&cells, ColorDepth::Ansi16),
+        "\x1b[31m\x1b[40ma\x1b[91m\x1b[107mb\x1b[0m\n"
+    );
+}
+
+#[test]
+fn only_changed_attributes_are_written() {
+    let bold = Style::new().bold();
+    let cells = [
+        ('a', bold),
+        ('b', bold.underline()),
+        ('c', bold),
+        ('d', Style::new().reverse()),
+    ];
+    assert_eq!(
+        ansi(&cells, ColorDepth::TrueColor),
+        "\x1b[1ma\x1b[4mb\x1b[24mc\x1b[0m\x1b[7md\x1b[0m\n"
+    );
+}
+
+#[test]
+fn reset_is_used_when_shorter() {
+    // Turning bold off would also need dim set again
+    let cells = [('a', Style::new().bold().dim()), ('b', Style::new().dim())];
+    assert_eq!(
+        ansi(&cells, ColorDepth::TrueColor),
+        "\x1b[1m\x1b[2ma\x1b[0m\x1b[2mb\x1b[0m\n"
+    );
+    // Color stays, so turning italic off is shorter
+    let red = Style::new().fg(Color::Red);
+    let cells = [('a', red.italic()), ('b', red)];
+    assert_eq!(
+        ansi(&cells, ColorDepth::TrueColor),
+        "\x1b[3m\x1b[38;5;9ma\x1b[23mb\x1b[0m\n"
+    );
+}
@@ -0,0 +1,64 @@
+use crossterm::style::{Attribute, Color};
+use rusty_ascii_graphics::{str_width, Align, RenderBuffer, Span, StyledText};
+
+fn render(width: usize, height: usize, text: StyledText) -> String {
+    let mut buffer = RenderBuffer::new(width, height);
+    buffer.draw(&text);
+    buffer.to_string()
+}
+
+#[test]
+fn wraps_between_words() {
+    let text = StyledText::new(0, 0, 10, 3, vec![Span::new("eat food and grow longer")]).wrap(true);
+    assert_eq!(render(10, 3, text), "eat food  \nand grow  \nlonger    \n");
+}
+
+#[test]
+fn breaks_words_longer_than_line() {
+    let text = StyledText::new(0, 0, 4, 3, vec![Span::new("snakesnake")]).wrap(true);
+    assert_eq!(render(4, 3, text), "snak\nesna\nke  \n");
+}
+
+#[test]
+fn cut_lines_end_with_ellipsis() {
+    let spans = vec![Span::new("first line\nsecond line\nthird line")];
+    let text = StyledText::new(0, 0, 8, 2, spans).ellipsis("…");
+    assert_eq!(render(8, 2, text), "first l…\nsecond… \n");
+}
+
+#[test]
+fn aligns_lines() {
+    let text = StyledText::new(0, 0, 7, 2, vec![Span::new("ab\ncde")]).align(Align::Right);
+    assert_eq!(render(7, 2, text), "     ab\n    cde\n");
+    let text = StyledText::new(0, 0, 7, 2, vec![Span::new("ab\ncde")]).align(Align::Center);
+    assert_eq!(render(7, 2, text), "  ab   \n  cde  \n");
+}
+
+#[test]
+fn spans_keep_their_style() {
+    let spans = vec![
+        Span::new("name").fg(Color::Red).bold(),
+        Span::new(": hi").underline(),
+    ];
+    let mut buffer = RenderBuffer::new(8, 1);
+    buffer.draw(&StyledText::new(0, 0, 8, 1, spans));
+    let name = buffer.get(0, 0);
+    assert_eq!(name.style.fg, Some(Color::Red));
+    assert!(name.style.has(Attribute::Bold));
+    let text = buffer.get(6, 0);
+    assert_eq!(text.style.fg, None);
+    assert!(text.style.has(Attribute::Underlined));
+    assert!(buffer
+        .to_ansi()
+        .starts_with("\x1b[1m\x1b[38;5;9mname\x1b[0m\x1b[4m: hi"));
+}
+
+#[test]
+fn wide_chars_take_two_columns() {
+    assert_eq!(str_width("食べ物"), 6);
+    let text = StyledText::new(0, 0, 5, 2, vec![Span::new("食べ物 x")]).ellipsis("…");
+    let mut buffer = RenderBuffer::new(5, 2);
+    buffer.draw(&text);
+    // Cell covered by wide char is skipped in output
+    assert_eq!(buffer.to_string(), "食べ…\n     \n");
+}
@@ -0,0 +1,97 @@
+use crossterm::style::Color;
+
+#[allow(dead_code)]
+#[path = "../src/bin/client/theme.rs"]
+mod theme;
+
+use theme::{parse_color, Theme};
+
+const PLAYERS: &str = r##"players = [["red", "#00ff00"]]"##;
+
+fn theme_with(colors: &str) -> Result<Theme, String> {
+    Theme::from_toml(&format!("{}\n{}", colors, PLAYERS))
+}
+
+const COLORS: &str = r##"
+border = "blue"
+food = 9
+text = "reset"
+title = "#102030"
+highlight = "dark_yellow"
+warning = "red"
+"##;
+
+#[test]
+fn colors_parse_in_every_notation() {
+    let color = |s: &str| parse_color(&toml::Value::String(s.to_string()));
+    assert_eq!(color("dark_green"), Some(Color::DarkGreen));
+    assert_eq!(color("reset"), Some(Color::Reset));
+    assert_eq!(
+        color("#ff8000"),
+        Some(Color::Rgb {
+            r: 255,
+            g: 128,
+            b: 0
+        })
+    );
+    assert_eq!(
+        parse_color(&toml::Value::Integer(208)),
+        Some(Color::AnsiValue(208))
+    );
+}
+
+#[test]
+fn malformed_colors_are_rejected() {
+    let color = |s: &str| parse_color(&toml::Value::String(s.to_string()));
+    assert_eq!(color("#12345"), None);
+    assert_eq!(color("#1234567"), None);
+    assert_eq!(color("#gg0000"), None);
+    assert_eq!(color("purplish"), None);
+    assert_eq!(parse_color(&toml::Value::Integer(256)), None);
+    assert_eq!(parse_color(&toml::Value::Boolean(true)), None);
+}
+
+#[test]
+fn theme_is_read_from_toml() {
+    let theme = theme_with(COLORS).unwrap();
+    assert_eq!(theme.border, Color::Blue);
+    assert_eq!(theme.food, Color::AnsiValue(9));
+    assert_eq!(theme.text, Color::Reset);
+    assert_eq!(
+        theme.title,
+        Color::Rgb {
+            r: 0x10,
+            g: 0x20,
+            b: 0x30
+        }
+    );
+    assert_eq!(
+        theme.player(3),
+        (Color::Red, Color::Rgb { r: 0, g: 255, b: 0 })
+    );
+}
+
+#[test]
+fn bad_theme_files_are_errors() {
+    let bad_color = COLORS.replace("\"blue\"", "\"#blue\"");
+    assert!(theme_with(&bad_color).err().unwrap().contains("border"));
+    let missing = COLORS.replace("food = 9\n", "");
+    assert!(theme_with(&missing).err().unwrap().contains("food"));
+    let single = format!("{}\nplayers = [[\"red\"]]", COLORS);
+    assert!(Theme::from_toml(&single).is_err());
+    let empty = format!("{}\nplayers = []", COLORS);
+    assert!(Theme::from_toml(&empty).is_err());
+}
+
+#[test]
+fn colorblind_players_stand_out() {
+    let theme = Theme::load("colorblind").unwrap();
+    let mut seen = vec![theme.border, theme.food, theme.title];
+    for &(body, head) in &theme.players {
+        assert_ne!(body, head);
+        assert!(!seen.contains(&body), "{:?} used twice", body);
+        assert!(!seen.contains(&head), "{:?} used twice", head);
+        seen.push(body);
+        seen.push(head);
+    }
+}
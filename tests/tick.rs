@@ -0,0 +1,86 @@
+use std::time::{Duration, Instant};
+
+use rusty_snake::chat::command;
+use rusty_snake::tick::{LagPolicy, Ticker, MAX_TICK_RATE};
+use rusty_snake::{Game, SnakeEventType};
+
+const PERIOD: Duration = Duration::from_millis(100);
+
+#[test]
+fn work_time_does_not_stretch_period() {
+    let start = Instant::now();
+    let mut ticker = Ticker::starting_at(10, LagPolicy::CatchUp(5), start);
+    assert_eq!(ticker.deadline(), start + PERIOD);
+
+    // Woke up 30ms late after simulating and sending
+    assert_eq!(
+        ticker.advance(start + PERIOD + Duration::from_millis(30)),
+        1
+    );
+    assert_eq!(ticker.deadline(), start + PERIOD * 2);
+    assert_eq!(ticker.advance(start + PERIOD * 2), 1);
+    assert_eq!(ticker.deadline(), start + PERIOD * 3);
+    assert_eq!(ticker.tick(), 2);
+}
+
+#[test]
+fn catch_up_runs_missed_ticks_up_to_cap() {
+    let start = Instant::now();
+    let mut ticker = Ticker::starting_at(10, LagPolicy::CatchUp(3), start);
+
+    // Two ticks missed, both run
+    assert_eq!(ticker.advance(start + PERIOD * 3), 3);
+    assert_eq!(ticker.deadline(), start + PERIOD * 4);
+
+    // Five missed, only three run and the rest are dropped
+    assert_eq!(ticker.advance(start + PERIOD * 9), 3);
+    assert_eq!(ticker.deadline(), start + PERIOD * 10);
+    assert_eq!(ticker.tick(), 6);
+}
+
+#[test]
+fn skip_drops_missed_ticks() {
+    let start = Instant::now();
+    let mut ticker = Ticker::starting_at(10, LagPolicy::Skip, start);
+
+    assert_eq!(
+        ticker.advance(start + PERIOD * 5 + Duration::from_millis(50)),
+        1
+    );
+    assert_eq!(ticker.deadline(), start + PERIOD * 6);
+    assert_eq!(ticker.tick(), 1);
+}
+
+fn tick_rate(rate: u32) -> SnakeEventType {
+    SnakeEventType::TickRate {
+        rate,
+        secret: String::new(),
+    }
+}
+
+#[test]
+fn tick_rate_event_is_clamped() {
+    let mut game = Game::new(20, 20, 0, 1, true);
+    game.handle_events(tick_rate(20), "admin".to_string());
+    assert_eq!(game.tick_rate, 20);
+    game.handle_events(tick_rate(0), "admin".to_string());
+    assert_eq!(game.tick_rate, 1);
+    game.handle_events(tick_rate(10_000), "admin".to_string());
+    assert_eq!(game.tick_rate, MAX_TICK_RATE);
+}
+
+#[test]
+fn tick_rate_command_parses() {
+    assert!(matches!(
+        command("/tick_rate 15 hunter2".to_string()),
+        SnakeEventType::TickRate { rate: 15, secret } if secret == "hunter2"
+    ));
+    assert!(matches!(
+        command("/tick_rate 15".to_string()),
+        SnakeEventType::TickRate { rate: 15, secret } if secret.is_empty()
+    ));
+    assert!(matches!(
+        command("/tick_rate fast".to_string()),
+        SnakeEventType::Chat(text) if text == "/tick_rate fast"
+    ));
+}
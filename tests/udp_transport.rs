@@ -0,0 +1,153 @@
+use std::time::Duration;
+
+use tokio::net::UdpSocket;
+
+use rusty_snake::net::{Packet, UdpClient, UdpServer};
+use rusty_snake::{Direction, Game, Signal, SnakeEvent, SnakeEventType};
+
+fn event(event_type: SnakeEventType) -> SnakeEvent {
+    SnakeEvent {
+        event_type,
+        event_owner: "player".to_string(),
+    }
+}
+
+async fn pair() -> (UdpServer, UdpClient) {
+    let server = UdpServer::bind("127.0.0.1:0").await.unwrap();
+    let client = UdpClient::connect(server.local_addr().unwrap())
+        .await
+        .unwrap();
+    (server, client)
+}
+
+#[tokio::test]
+async fn events_are_delivered_and_acknowledged() {
+    let (mut server, mut client) = pair().await;
+    client
+        .send(event(SnakeEventType::Signal(Signal::Connect)))
+        .await
+        .unwrap();
+    let received = server.recv().await.unwrap();
+    assert!(matches!(
+        received.event_type,
+        SnakeEventType::Signal(Signal::Connect)
+    ));
+    assert_eq!(server.peers(), 1);
+
+    let mut game = Game::new(10, 10, 0, 1, true);
+    game.step();
+    server.broadcast(&game).await.unwrap();
+    let snapshot = client.recv().await.unwrap();
+    assert_eq!(snapshot.tick, 1);
+    assert_eq!(client.pending(), 0);
+}
+
+#[tokio::test]
+async fn repeated_events_are_delivered_once() {
+    let (mut server, mut client) = pair().await;
+    client
+        .send(event(SnakeEventType::Movement(Direction::Up)))
+        .await
+        .unwrap();
+    server.recv().await.unwrap();
+    // Ack not processed yet, so first event is sent again
+    client.resend().await.unwrap();
+    client
+        .send(event(SnakeEventType::Movement(Direction::Left)))
+        .await
+        .unwrap();
+    let received = server.recv().await.unwrap();
+    assert!(matches!(
+        received.event_type,
+        SnakeEventType::Movement(Direction::Left)
+    ));
+}
+
+#[tokio::test]
+async fn stale_snapshots_are_dropped() {
+    let fake_server = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    let mut client = UdpClient::connect(fake_server.local_addr().unwrap())
+        .await
+        .unwrap();
+    client.resend().await.unwrap();
+    let mut buf = [0u8; 64];
+    let (_, client_addr) = fake_server.recv_from(&mut buf).await.unwrap();
+
+    for seq in [2, 1, 3] {
+        let mut game = Game::new(10, 10, 0, 1, true);
+        game.tick = seq as u64;
+        let packet = bincode::serialize(&Packet::Snapshot { seq, game }).unwrap();
+        fake_server.send_to(&packet, client_addr).await.unwrap();
+    }
+    assert_eq!(client.recv().await.unwrap().tick, 2);
+    assert_eq!(client.recv().await.unwrap().tick, 3);
+}
+
+#[tokio::test]
+async fn idle_peers_are_disconnected() {
+    let (mut server, mut client) = pair().await;
+    client
+        .send(event(SnakeEventType::Signal(Signal::Connect)))
+        .await
+        .unwrap();
+    server.recv().await.unwrap();
+    tokio::time::sleep(Duration::from_millis(10)).await;
+
+    let events = server.drop_idle(Duration::ZERO);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].event_owner, "player");
+    assert!(matches!(
+        events[0].event_type,
+        SnakeEventType::Signal(Signal::Disconnect)
+    ));
+    assert_eq!(server.peers(), 0);
+}
+
+#[tokio::test]
+async fn big_maps_fit_in_datagram() {
+    let (mut server, mut client) = pair().await;
+    client
+        .send(event(SnakeEventType::Signal(Signal::Connect)))
+        .await
+        .unwrap();
+    server.recv().await.unwrap();
+
+    let mut game = Game::new(300, 150, 20, 2, true);
+    game.add_player("player".to_string());
+    game.add_missing_food();
+    game.handle_events(
+        SnakeEventType::Movement(Direction::Up),
+        "player".to_string(),
+    );
+    game.step();
+    server.broadcast(&game).await.unwrap();
+    let snapshot = client.recv().await.unwrap();
+
+    // Grid is left out of datagram and rebuilt from snakes and food
+    for x in 0..300 {
+        for y in 0..150 {
+            assert_eq!(
+                format!("{:?}", snapshot.cells.get_cell(x, y)),
+                format!("{:?}", game.cells.get_cell(x, y)),
+            );
+        }
+    }
+}
+
+#[tokio::test]
+async fn oversized_snapshots_are_refused() {
+    let (mut server, mut client) = pair().await;
+    client
+        .send(event(SnakeEventType::Signal(Signal::Connect)))
+        .await
+        .unwrap();
+    server.recv().await.unwrap();
+
+    let mut game = Game::new(300, 150, 0, 1, true);
+    game.add_player("player".to_string());
+    game.snakes[0].body = (0..300)
+        .flat_map(|x| (0..150).map(move |y| (x, y)))
+        .collect();
+    let error = server.broadcast(&game).await.unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+}
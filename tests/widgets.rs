@@ -0,0 +1,150 @@
+use crossterm::event::{
+    Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+use rusty_ascii_graphics::{
+    Button, Checkbox, Drawable, Focus, ListView, Rect, RenderBuffer, Response, TextInput, Widget,
+};
+
+fn key(code: KeyCode) -> Event {
+    Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+}
+
+fn click(x: u16, y: u16) -> Event {
+    Event::Mouse(MouseEvent {
+        kind: MouseEventKind::Down(MouseButton::Left),
+        column: x,
+        row: y,
+        modifiers: KeyModifiers::NONE,
+    })
+}
+
+fn type_keys(input: &mut TextInput, codes: &[KeyCode]) {
+    for &code in codes {
+        input.handle_key(&KeyEvent::new(code, KeyModifiers::NONE));
+    }
+}
+
+fn render(width: usize, height: usize, widget: &dyn Drawable) -> String {
+    let mut buffer = RenderBuffer::new(width, height);
+    widget.draw(&mut buffer);
+    buffer.to_string()
+}
+
+#[test]
+fn text_input_edits_at_cursor() {
+    let mut input = TextInput::new(0, 0, 10);
+    input.set_value("sake");
+    type_keys(
+        &mut input,
+        &[
+            KeyCode::Home,
+            KeyCode::Right,
+            KeyCode::Char('n'),
+            KeyCode::End,
+            KeyCode::Backspace,
+            KeyCode::Left,
+            KeyCode::Delete,
+        ],
+    );
+    assert_eq!(input.value(), "sna");
+    assert_eq!(input.cursor(), 3);
+}
+
+#[test]
+fn text_input_scrolls_to_cursor() {
+    let mut input = TextInput::new(0, 0, 5);
+    input.set_value("rusty snake");
+    assert_eq!(render(5, 1, &input), "nake \n");
+    type_keys(&mut input, &[KeyCode::Home, KeyCode::Right]);
+    assert_eq!(render(5, 1, &input), "rusty\n");
+    // Only as far as cursor on `s` needs
+    type_keys(&mut input, &[KeyCode::Right; 5]);
+    assert_eq!(render(5, 1, &input), "sty s\n");
+}
+
+#[test]
+fn text_input_keeps_max_len() {
+    let mut input = TextInput::new(0, 0, 10).max_len(3);
+    let codes: Vec<KeyCode> = "snake".chars().map(KeyCode::Char).collect();
+    type_keys(&mut input, &codes);
+    assert_eq!(input.value(), "sna");
+}
+
+#[test]
+fn list_scrolls_to_selection() {
+    let items = (1..=5).map(|i| format!("room {}", i)).collect();
+    let mut list = ListView::new(Rect::new(0, 0, 6, 2), items);
+    for _ in 0..3 {
+        assert_eq!(
+            list.handle_key(&KeyEvent::new(KeyCode::Down, KeyModifiers::NONE)),
+            Response::Changed
+        );
+    }
+    assert_eq!(list.selected(), Some(3));
+    assert_eq!(render(6, 2, &list), "room 3\nroom 4\n");
+    list.handle_key(&KeyEvent::new(KeyCode::End, KeyModifiers::NONE));
+    // Nothing to move to, so focus may move on
+    let down = KeyEvent::new(KeyCode::Down, KeyModifiers::NONE);
+    assert_eq!(list.handle_key(&down), Response::Ignored);
+}
+
+#[test]
+fn focus_moves_on_keys_widgets_ignore() {
+    let mut name = TextInput::new(0, 0, 10);
+    let mut ok = Button::new(0, 1, "Ok");
+    let mut sound = Checkbox::new(0, 2, "Sound", false);
+    let mut focus = Focus::new();
+    let mut widgets: [&mut dyn Widget; 3] = [&mut name, &mut ok, &mut sound];
+    assert_eq!(
+        focus.handle(&key(KeyCode::Char('a')), &mut widgets),
+        Some((0, Response::Changed))
+    );
+    assert_eq!(
+        focus.handle(&key(KeyCode::Tab), &mut widgets),
+        Some((1, Response::Handled))
+    );
+    assert_eq!(
+        focus.handle(&key(KeyCode::Down), &mut widgets),
+        Some((2, Response::Handled))
+    );
+    assert_eq!(
+        focus.handle(&key(KeyCode::Char(' ')), &mut widgets),
+        Some((2, Response::Changed))
+    );
+    // Wraps around
+    assert_eq!(
+        focus.handle(&key(KeyCode::Tab), &mut widgets),
+        Some((0, Response::Handled))
+    );
+    assert_eq!(focus.handle(&key(KeyCode::Esc), &mut widgets), None);
+    assert_eq!(name.value(), "a");
+    assert!(sound.checked());
+}
+
+#[test]
+fn click_focuses_widget_under_pointer() {
+    let mut list = ListView::new(Rect::new(0, 0, 8, 3), vec!["a".into(), "b".into()]);
+    let mut ok = Button::new(0, 3, "Ok");
+    let mut focus = Focus::new();
+    let mut widgets: [&mut dyn Widget; 2] = [&mut list, &mut ok];
+    assert_eq!(
+        focus.handle(&click(2, 1), &mut widgets),
+        Some((0, Response::Changed))
+    );
+    // Clicking selected item activates it
+    assert_eq!(
+        focus.handle(&click(2, 1), &mut widgets),
+        Some((0, Response::Activated))
+    );
+    assert_eq!(focus.handle(&click(7, 3), &mut widgets), None);
+    assert_eq!(
+        focus.handle(&click(5, 3), &mut widgets),
+        Some((1, Response::Activated))
+    );
+    assert_eq!(focus.focused(), Some(1));
+    assert_eq!(list.selected(), Some(1));
+    assert_eq!(
+        render(8, 4, &ok),
+        "        \n        \n        \n[ Ok ]  \n"
+    );
+}
@@ -0,0 +1,68 @@
+use futures_util::{SinkExt, StreamExt};
+use tokio::net::TcpListener;
+use tokio_tungstenite::tungstenite::Message;
+
+use rusty_snake::net::{WsConnection, WsFormat};
+use rusty_snake::{Direction, Game, Signal, SnakeEvent, SnakeEventType};
+
+fn event(event_type: SnakeEventType) -> SnakeEvent {
+    SnakeEvent {
+        event_type,
+        event_owner: "player".to_string(),
+    }
+}
+
+// Event sent by client, snapshot sent back, then client closes
+async fn round_trip(path: &str, format: WsFormat) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("ws://{}{}", listener.local_addr().unwrap(), path);
+    let server = tokio::spawn(async move {
+        let (socket, _) = listener.accept().await.unwrap();
+        let mut connection = WsConnection::accept(socket).await.unwrap();
+        assert_eq!(connection.format(), format);
+        let received = connection.recv_event().await.unwrap();
+        let mut game = Game::new(10, 10, 0, 1, true);
+        game.tick = 7;
+        connection.send_game(&game).await.unwrap();
+        assert!(connection.recv_event().await.is_none());
+        (received, connection.disconnect_events())
+    });
+
+    let (mut client, _) = tokio_tungstenite::connect_async(url).await.unwrap();
+    let sent = event(SnakeEventType::Movement(Direction::Left));
+    let message = match format {
+        WsFormat::Json => Message::Text(serde_json::to_string(&sent).unwrap()),
+        WsFormat::Binary => Message::Binary(bincode::serialize(&sent).unwrap()),
+    };
+    client.send(message).await.unwrap();
+    let game: Game = match client.next().await.unwrap().unwrap() {
+        Message::Text(text) if format == WsFormat::Json => serde_json::from_str(&text).unwrap(),
+        Message::Binary(data) if format == WsFormat::Binary => bincode::deserialize(&data).unwrap(),
+        other => panic!("unexpected message {:?}", other),
+    };
+    assert_eq!(game.tick, 7);
+    client.close(None).await.unwrap();
+
+    let (received, disconnects) = server.await.unwrap();
+    assert_eq!(received.event_owner, "player");
+    assert!(matches!(
+        received.event_type,
+        SnakeEventType::Movement(Direction::Left)
+    ));
+    assert_eq!(disconnects.len(), 1);
+    assert_eq!(disconnects[0].event_owner, "player");
+    assert!(matches!(
+        disconnects[0].event_type,
+        SnakeEventType::Signal(Signal::Disconnect)
+    ));
+}
+
+#[tokio::test]
+async fn json_round_trip() {
+    round_trip("/", WsFormat::Json).await;
+}
+
+#[tokio::test]
+async fn binary_round_trip() {
+    round_trip("/binary", WsFormat::Binary).await;
+}